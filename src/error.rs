@@ -0,0 +1,140 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::messages::AlicaMessageValidationError;
+use crate::payloads;
+use crate::TransactionFamilyError;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ErrorReason {
+    MissingField,
+    WrongType,
+    OutOfRange,
+    NotBase64,
+    Malformed,
+    DisallowedValue,
+    Conflict,
+    Unsupported,
+    UnknownField,
+    UnknownMessageType,
+    InvalidTimestamp,
+    Io
+}
+
+impl ErrorReason {
+    pub fn code(&self) -> u8 {
+        match self {
+            ErrorReason::MissingField => 1,
+            ErrorReason::WrongType => 2,
+            ErrorReason::OutOfRange => 3,
+            ErrorReason::NotBase64 => 4,
+            ErrorReason::Malformed => 5,
+            ErrorReason::DisallowedValue => 6,
+            ErrorReason::Conflict => 7,
+            ErrorReason::Unsupported => 8,
+            ErrorReason::UnknownField => 9,
+            ErrorReason::UnknownMessageType => 10,
+            ErrorReason::InvalidTimestamp => 11,
+            ErrorReason::Io => 12
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CrateError {
+    Payload(payloads::Error),
+    Validation(AlicaMessageValidationError),
+    Family(TransactionFamilyError),
+    TooShort { expected: usize, actual: usize }
+}
+
+impl From<payloads::Error> for CrateError {
+    fn from(error: payloads::Error) -> Self {
+        CrateError::Payload(error)
+    }
+}
+
+impl From<AlicaMessageValidationError> for CrateError {
+    fn from(error: AlicaMessageValidationError) -> Self {
+        CrateError::Validation(error)
+    }
+}
+
+impl From<TransactionFamilyError> for CrateError {
+    fn from(error: TransactionFamilyError) -> Self {
+        CrateError::Family(error)
+    }
+}
+
+impl Display for CrateError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
+        match self {
+            CrateError::Payload(error) => write!(formatter, "{}", error),
+            CrateError::Validation(error) => write!(formatter, "{}", error),
+            CrateError::Family(error) => write!(formatter, "{}", error),
+            CrateError::TooShort { expected, actual } =>
+                write!(formatter, "Expected at least {} characters but got {}", expected, actual)
+        }
+    }
+}
+
+impl std::error::Error for CrateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CrateError::Payload(error) => Some(error),
+            CrateError::Validation(error) => Some(error),
+            CrateError::Family(error) => Some(error),
+            CrateError::TooShort { .. } => None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::error::Error;
+
+    use crate::CrateError;
+    use crate::messages::AlicaMessageValidationError;
+    use crate::payloads;
+    use crate::TransactionFamilyError;
+
+    #[test]
+    fn payload_errors_convert_into_payload_crate_errors() {
+        let error: CrateError = payloads::Error::InvalidTimestamp.into();
+
+        assert!(matches!(error, CrateError::Payload(payloads::Error::InvalidTimestamp)))
+    }
+
+    #[test]
+    fn validation_errors_convert_into_validation_crate_errors() {
+        let error: CrateError = AlicaMessageValidationError::MissingField("senderId".to_string()).into();
+
+        match error {
+            CrateError::Validation(AlicaMessageValidationError::MissingField(field)) => assert_eq!(field, "senderId"),
+            _ => panic!("Expected a validation error")
+        }
+    }
+
+    #[test]
+    fn family_errors_convert_into_family_crate_errors() {
+        let error: CrateError = TransactionFamilyError::MissingName.into();
+
+        assert!(matches!(error, CrateError::Family(TransactionFamilyError::MissingName)))
+    }
+
+    #[test]
+    fn crate_errors_expose_the_wrapped_error_as_source() {
+        let error: CrateError = TransactionFamilyError::MissingVersion.into();
+
+        let source = error.source().expect("Expected a source error");
+
+        assert_eq!(source.to_string(), error.to_string())
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn crate_errors_display_the_wrapped_error() {
+        let error: CrateError = AlicaMessageValidationError::MissingField("senderId".to_string()).into();
+
+        assert_eq!(error.to_string(), "Required field missing: senderId")
+    }
+}
@@ -1,8 +1,356 @@
+use std::collections::BTreeMap;
+
 use sha2::Digest;
 
+use crate::error::CrateError;
+use crate::TransactionFamilyError;
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum HashAlgorithm {
+    Sha256,
+    #[default]
+    Sha512
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum AddressCase {
+    #[default]
+    Lower,
+    Upper
+}
+
+impl AddressCase {
+    pub fn apply(&self, hex: &str) -> String {
+        match self {
+            AddressCase::Lower => hex.to_lowercase(),
+            AddressCase::Upper => hex.to_uppercase()
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum AddressLayout {
+    #[default]
+    Flat,
+    AgentPrefixed
+}
+
+// Length of a hex encoded SHA-256 checksum, the shortest one any hash algorithm produces
+pub const MIN_CHECKSUM_LENGTH: usize = 64;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct AddressScheme {
+    prefix_length: usize,
+    suffix_length: usize
+}
+
+impl AddressScheme {
+    pub fn new(prefix_length: usize, suffix_length: usize) -> Result<Self, CrateError> {
+        if prefix_length > MIN_CHECKSUM_LENGTH || suffix_length > MIN_CHECKSUM_LENGTH {
+            return Err(TransactionFamilyError::InvalidAddressScheme.into());
+        }
+        Ok(AddressScheme {
+            prefix_length,
+            suffix_length
+        })
+    }
+
+    pub fn sawtooth() -> Self {
+        AddressScheme {
+            prefix_length: 6,
+            suffix_length: 64
+        }
+    }
+
+    pub fn prefix_length(&self) -> usize {
+        self.prefix_length
+    }
+
+    pub fn suffix_length(&self) -> usize {
+        self.suffix_length
+    }
+
+    pub fn address_length(&self) -> usize {
+        self.prefix_length + self.suffix_length
+    }
+
+    // Every checksum is long enough for a scheme, so the whole checksum is never returned
+    pub fn prefix_of<'a>(&self, checksum: &'a str) -> &'a str {
+        checksum.get(..self.prefix_length).unwrap_or(checksum)
+    }
+
+    pub fn suffix_of<'a>(&self, checksum: &'a str) -> &'a str {
+        checksum.get(..self.suffix_length).unwrap_or(checksum)
+    }
+}
+
+impl Default for AddressScheme {
+    fn default() -> Self {
+        AddressScheme::sawtooth()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum AgentIdPolicy {
+    #[default]
+    Reject,
+    Strip,
+    PercentEncode
+}
+
+impl AgentIdPolicy {
+    pub fn apply(&self, agent_id: &str) -> Option<String> {
+        match self {
+            AgentIdPolicy::Reject if agent_id.chars().any(char::is_control) => None,
+            AgentIdPolicy::Reject => Some(agent_id.to_string()),
+            AgentIdPolicy::Strip => Some(agent_id.chars().filter(|c| !c.is_control()).collect()),
+            AgentIdPolicy::PercentEncode => {
+                let mut encoded = String::with_capacity(agent_id.len());
+                for c in agent_id.chars() {
+                    if c.is_control() || c == '%' {
+                        let mut buffer = [0; 4];
+                        for byte in c.encode_utf8(&mut buffer).bytes() {
+                            encoded.push_str(&format!("%{:02X}", byte));
+                        }
+                    } else {
+                        encoded.push(c);
+                    }
+                }
+                Some(encoded)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum TimestampUnit {
+    #[default]
+    Seconds,
+    Milliseconds,
+    Microseconds,
+    Nanoseconds
+}
+
+impl TimestampUnit {
+    pub fn as_secs(&self, timestamp: u64) -> u64 {
+        match self {
+            TimestampUnit::Seconds => timestamp,
+            TimestampUnit::Milliseconds => timestamp / 1_000,
+            TimestampUnit::Microseconds => timestamp / 1_000_000,
+            TimestampUnit::Nanoseconds => timestamp / 1_000_000_000
+        }
+    }
+
+    pub fn as_millis(&self, timestamp: u64) -> Option<u64> {
+        match self {
+            TimestampUnit::Seconds => timestamp.checked_mul(1_000),
+            TimestampUnit::Milliseconds => Some(timestamp),
+            TimestampUnit::Microseconds => Some(timestamp / 1_000),
+            TimestampUnit::Nanoseconds => Some(timestamp / 1_000_000)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct NormalizationOptions {
+    pub trim: bool,
+    pub lowercase: bool,
+    pub timestamp_unit: TimestampUnit,
+    pub canonical_json: bool
+}
+
+impl NormalizationOptions {
+    pub fn normalize_field(&self, field: &str) -> String {
+        let field = if self.trim { field.trim() } else { field };
+        if self.lowercase { field.to_lowercase() } else { field.to_string() }
+    }
+
+    pub fn normalize_timestamp(&self, timestamp: u64) -> u64 {
+        self.timestamp_unit.as_secs(timestamp)
+    }
+
+    pub fn normalize_message(&self, message: &[u8]) -> Vec<u8> {
+        if !self.canonical_json {
+            return message.to_vec();
+        }
+        match std::str::from_utf8(message).ok().and_then(|message| json::parse(message).ok()) {
+            Some(message_json) => canonical_json(&message_json).into_bytes(),
+            None => message.to_vec()
+        }
+    }
+}
+
+pub fn canonical_json(value: &json::JsonValue) -> String {
+    sort_keys(value).dump()
+}
+
+fn sort_keys(value: &json::JsonValue) -> json::JsonValue {
+    match value {
+        json::JsonValue::Object(object) => {
+            let sorted_fields: BTreeMap<&str, &json::JsonValue> = object.iter().collect();
+            let mut sorted_object = json::object::Object::new();
+            for (field, field_value) in sorted_fields {
+                sorted_object.insert(field, sort_keys(field_value));
+            }
+            json::JsonValue::Object(sorted_object)
+        },
+        json::JsonValue::Array(entries) => json::JsonValue::Array(entries.iter().map(sort_keys).collect()),
+        _ => value.clone()
+    }
+}
+
+pub enum ChecksumBuilder {
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512)
+}
+
+impl ChecksumBuilder {
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => ChecksumBuilder::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha512 => ChecksumBuilder::Sha512(sha2::Sha512::new())
+        }
+    }
+
+    pub fn checksum<T>(&mut self, data: &T) -> String
+        where T: AsRef<[u8]> {
+        match self {
+            ChecksumBuilder::Sha256(hasher) => {
+                hasher.update(data);
+                data_encoding::HEXLOWER.encode(&hasher.finalize_reset()[..])
+            },
+            ChecksumBuilder::Sha512(hasher) => {
+                hasher.update(data);
+                data_encoding::HEXLOWER.encode(&hasher.finalize_reset()[..])
+            }
+        }
+    }
+}
+
 pub fn calculate_checksum<T>(data: &T) -> String
     where T: AsRef<[u8]> {
-    let mut hasher = sha2::Sha512::new();
-    hasher.update(data);
-    data_encoding::HEXLOWER.encode(&hasher.finalize()[..])
-}
\ No newline at end of file
+    calculate_checksum_with(HashAlgorithm::Sha512, data)
+}
+
+pub fn calculate_checksum_with<T>(algorithm: HashAlgorithm, data: &T) -> String
+    where T: AsRef<[u8]> {
+    ChecksumBuilder::new(algorithm).checksum(data)
+}
+
+pub fn take_prefix(s: &str, n: usize) -> Result<&str, CrateError> {
+    match s.char_indices().nth(n) {
+        Some((end, _)) => Ok(&s[..end]),
+        None if s.chars().count() == n => Ok(s),
+        None => Err(CrateError::TooShort { expected: n, actual: s.chars().count() })
+    }
+}
+
+pub const MAX_ERROR_CONTENT_LENGTH: usize = 64;
+
+pub fn truncate_for_error(content: &str) -> String {
+    match content.char_indices().nth(MAX_ERROR_CONTENT_LENGTH) {
+        Some((end, _)) => format!("{}...", &content[..end]),
+        None => content.to_string()
+    }
+}
+
+#[cfg(not(feature = "compact-errors"))]
+macro_rules! error_text {
+    ($($argument:tt)*) => { format!($($argument)*) };
+}
+
+#[cfg(feature = "compact-errors")]
+macro_rules! error_text {
+    ($($argument:tt)*) => {{
+        let _ = ($($argument)*,);
+        String::new()
+    }};
+}
+
+pub(crate) use error_text;
+
+#[cfg(test)]
+mod test {
+    use crate::error::CrateError;
+    use crate::helper::{calculate_checksum_with, canonical_json, take_prefix, truncate_for_error, ChecksumBuilder,
+                        HashAlgorithm, NormalizationOptions, TimestampUnit, MAX_ERROR_CONTENT_LENGTH};
+
+    #[test]
+    fn a_prefix_of_a_longer_string_can_be_taken() {
+        assert_eq!(take_prefix("abcdef", 3).unwrap(), "abc")
+    }
+
+    #[test]
+    fn a_prefix_of_the_whole_string_can_be_taken() {
+        assert_eq!(take_prefix("abc", 3).unwrap(), "abc")
+    }
+
+    #[test]
+    fn prefixes_respect_character_boundaries() {
+        assert_eq!(take_prefix("äöü", 2).unwrap(), "äö")
+    }
+
+    #[test]
+    fn a_prefix_longer_than_the_string_cannot_be_taken() {
+        match take_prefix("ab", 3) {
+            Err(CrateError::TooShort { expected: 3, actual: 2 }) => (),
+            _ => panic!("Expected a too short error")
+        }
+    }
+
+    #[test]
+    fn a_reused_checksum_builder_matches_fresh_checksums() {
+        let mut builder = ChecksumBuilder::new(HashAlgorithm::Sha512);
+
+        let first_checksum = builder.checksum(&"first");
+        let second_checksum = builder.checksum(&"second");
+
+        assert_eq!(first_checksum, calculate_checksum_with(HashAlgorithm::Sha512, &"first"));
+        assert_eq!(second_checksum, calculate_checksum_with(HashAlgorithm::Sha512, &"second"));
+    }
+
+    #[test]
+    fn short_error_content_is_not_truncated() {
+        assert_eq!(truncate_for_error("RoleSwitch"), "RoleSwitch")
+    }
+
+    #[test]
+    fn long_error_content_is_truncated_on_a_character_boundary() {
+        let content = "ä".repeat(MAX_ERROR_CONTENT_LENGTH * 10);
+
+        let truncated_content = truncate_for_error(&content);
+
+        assert_eq!(truncated_content, format!("{}...", "ä".repeat(MAX_ERROR_CONTENT_LENGTH)))
+    }
+
+    #[test]
+    fn converting_the_largest_timestamp_in_seconds_to_milliseconds_reports_overflow() {
+        assert_eq!(TimestampUnit::Seconds.as_millis(u64::MAX), None);
+        assert_eq!(TimestampUnit::Seconds.as_millis(u64::MAX / 1_000), Some(u64::MAX / 1_000 * 1_000));
+    }
+
+    #[test]
+    fn converting_the_largest_timestamp_to_coarser_units_is_exact() {
+        assert_eq!(TimestampUnit::Seconds.as_secs(u64::MAX), u64::MAX);
+        assert_eq!(TimestampUnit::Milliseconds.as_millis(u64::MAX), Some(u64::MAX));
+        assert_eq!(TimestampUnit::Nanoseconds.as_secs(u64::MAX), u64::MAX / 1_000_000_000);
+        assert_eq!(TimestampUnit::Nanoseconds.as_millis(u64::MAX), Some(u64::MAX / 1_000_000));
+    }
+
+    #[test]
+    fn canonical_json_sorts_keys_at_every_level() {
+        let message = json::parse(r#"{"b": [{"d": 1, "c": 2}], "a": {"f": true, "e": null}}"#).unwrap();
+
+        assert_eq!(canonical_json(&message), r#"{"a":{"e":null,"f":true},"b":[{"c":2,"d":1}]}"#)
+    }
+
+    #[test]
+    fn json_messages_differing_in_key_order_normalize_identically() {
+        let normalization = NormalizationOptions { canonical_json: true, ..NormalizationOptions::default() };
+
+        let message = normalization.normalize_message(r#"{"roleId": 1, "senderId": {"type": 0, "value": "id"}}"#.as_bytes());
+        let reordered_message = normalization.normalize_message(r#"{"senderId":{"value":"id","type":0},"roleId":1}"#.as_bytes());
+
+        assert_eq!(message, reordered_message)
+    }
+}
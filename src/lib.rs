@@ -1,33 +1,244 @@
-use crate::payloads::TransactionPayload;
+use std::collections::BTreeSet;
+
+use crate::payloads::{Format, TransactionPayload};
+use crate::helper::{AddressCase, AddressLayout, AddressScheme, AgentIdPolicy, ChecksumBuilder, HashAlgorithm,
+                    NormalizationOptions};
+use crate::messages::registry::ValidatorRegistry;
 
 pub mod messages;
 pub mod payloads;
 pub mod helper;
+pub mod error;
+
+pub use crate::error::CrateError;
+
+pub fn process_transaction(bytes: &[u8], format: &dyn Format, family: &TransactionFamily, registry: &ValidatorRegistry)
+                           -> Result<(TransactionPayload, String), CrateError> {
+    let payload = format.deserialize(bytes)?;
+    registry.validate(&payload.message_type, &payload.message_bytes)?;
+    let address = family.try_calculate_state_address_for(&payload)?;
+    Ok((payload, address))
+}
+
+pub const AGENT_SEGMENT_LENGTH: usize = 16;
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct TransactionFamily {
     pub name: String,
-    pub versions: Vec<String>
+    pub versions: Vec<String>,
+    pub hash_algorithm: HashAlgorithm,
+    pub address_case: AddressCase,
+    pub address_layout: AddressLayout,
+    pub address_scheme: AddressScheme,
+    pub normalization: NormalizationOptions,
+    pub agent_id_policy: AgentIdPolicy
+}
+
+#[derive(PartialEq, Debug)]
+pub struct AddressDerivation {
+    pub namespace_input: String,
+    pub payload_input: String,
+    pub namespace_checksum: String,
+    pub payload_checksum: String,
+    pub address: String
+}
+
+#[derive(PartialEq, Debug)]
+pub enum TransactionFamilyError {
+    MissingName,
+    MissingVersion,
+    MismatchedAddressCase,
+    InvalidAgentId,
+    InvalidAddressScheme
 }
 
+impl std::fmt::Display for TransactionFamilyError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            TransactionFamilyError::MissingName => "Transaction family has no name",
+            TransactionFamilyError::MissingVersion => "Transaction family has no version",
+            TransactionFamilyError::MismatchedAddressCase => "Address does not match the configured case",
+            TransactionFamilyError::InvalidAgentId => "Agent id contains control characters",
+            TransactionFamilyError::InvalidAddressScheme => "Address scheme does not fit the checksum length or address layout",
+        };
+
+        write!(formatter, "{}", message)
+    }
+}
+
+impl std::error::Error for TransactionFamilyError {}
+
 impl TransactionFamily {
     pub fn new(name: &str, versions: &[String]) -> Self {
         TransactionFamily {
             name: name.to_string(),
-            versions: versions.to_vec()
+            versions: versions.to_vec(),
+            ..TransactionFamily::default()
         }
     }
 
+    pub fn builder() -> TransactionFamilyBuilder {
+        TransactionFamilyBuilder::default()
+    }
+
+    pub fn with_normalization(mut self, normalization: NormalizationOptions) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    pub fn with_agent_id_policy(mut self, agent_id_policy: AgentIdPolicy) -> Self {
+        self.agent_id_policy = agent_id_policy;
+        self
+    }
+
     pub fn calculate_namespace(&self) -> String {
-        let namespace_part = helper::calculate_checksum(&self.name);
-        namespace_part[..6].to_string()
+        let checksum = helper::calculate_checksum_with(self.hash_algorithm, &self.name);
+        self.address_case.apply(self.address_scheme.prefix_of(&checksum))
+    }
+
+    pub fn try_calculate_namespace(&self) -> Result<String, CrateError> {
+        if self.name.is_empty() {
+            return Err(TransactionFamilyError::MissingName.into());
+        }
+        let checksum = helper::calculate_checksum_with(self.hash_algorithm, &self.name);
+        self.namespace_from(&checksum)
+    }
+
+    fn namespace_from(&self, checksum: &str) -> Result<String, CrateError> {
+        let namespace_part = helper::take_prefix(checksum, self.address_scheme.prefix_length())?;
+        Ok(self.address_case.apply(namespace_part))
     }
 
     pub fn calculate_state_address_for(&self, message: &TransactionPayload) -> String {
-        let payload_part = helper::calculate_checksum(
-            &format!("{}{}{}", &message.agent_id, &message.message_type, &message.timestamp));
+        let payload_part = self.payload_checksum(&mut ChecksumBuilder::new(self.hash_algorithm), message);
         let namespace_part = self.calculate_namespace();
-        format!("{}{}", &namespace_part[..6], &payload_part[..64])
+        self.state_address_from(&namespace_part, &payload_part)
+    }
+
+    pub fn try_calculate_state_address_for(&self, message: &TransactionPayload) -> Result<String, CrateError> {
+        self.validate_address_scheme()?;
+        self.agent_id_policy.apply(&message.agent_id).ok_or(TransactionFamilyError::InvalidAgentId)?;
+        Ok(self.calculate_state_address_for(message))
+    }
+
+    pub fn addresses_for(&self, messages: &[TransactionPayload]) -> Vec<String> {
+        let namespace_part = self.calculate_namespace();
+        let mut checksum_builder = ChecksumBuilder::new(self.hash_algorithm);
+
+        messages.iter()
+            .map(|message| {
+                let payload_part = self.payload_checksum(&mut checksum_builder, message);
+                self.state_address_from(&namespace_part, &payload_part)
+            })
+            .collect()
+    }
+
+    pub fn matches_expected(&self, payload: &TransactionPayload, expected_address: &str) -> bool {
+        self.calculate_state_address_for(payload) == expected_address
+    }
+
+    pub fn find_address_collisions(&self, messages: &[TransactionPayload]) -> Vec<(usize, usize)> {
+        let addresses = self.addresses_for(messages);
+
+        let mut collisions = Vec::new();
+        for (index, address) in addresses.iter().enumerate() {
+            for (other_index, other_address) in addresses.iter().enumerate().skip(index + 1) {
+                if address == other_address {
+                    collisions.push((index, other_index));
+                }
+            }
+        }
+        collisions
+    }
+
+    pub fn address_bytes_for(&self, messages: &[TransactionPayload]) -> usize {
+        let addresses: BTreeSet<String> = self.addresses_for(messages).into_iter().collect();
+        addresses.iter().map(|address| address.len()).sum()
+    }
+
+    pub fn prefix_for_agent(&self, agent_id: &str) -> String {
+        let namespace_part = self.calculate_namespace();
+        match self.address_layout {
+            AddressLayout::Flat => namespace_part,
+            AddressLayout::AgentPrefixed => {
+                let agent_part = self.agent_checksum(&mut ChecksumBuilder::new(self.hash_algorithm), agent_id);
+                format!("{}{}", namespace_part, self.address_case.apply(&agent_part[..AGENT_SEGMENT_LENGTH]))
+            }
+        }
+    }
+
+    fn payload_checksum(&self, checksum_builder: &mut ChecksumBuilder, payload: &TransactionPayload) -> String {
+        let checksum = checksum_builder.checksum(&self.address_input_for(payload));
+        match self.address_layout {
+            AddressLayout::Flat => checksum,
+            AddressLayout::AgentPrefixed => {
+                let agent_part = self.agent_checksum(checksum_builder, &payload.agent_id);
+                format!("{}{}", &agent_part[..AGENT_SEGMENT_LENGTH], checksum)
+            }
+        }
+    }
+
+    fn agent_checksum(&self, checksum_builder: &mut ChecksumBuilder, agent_id: &str) -> String {
+        checksum_builder.checksum(&self.normalization.normalize_field(&self.address_agent_id(agent_id)))
+    }
+
+    fn address_agent_id(&self, agent_id: &str) -> String {
+        self.agent_id_policy.apply(agent_id).unwrap_or_else(|| agent_id.to_string())
+    }
+
+    pub fn address_input_for(&self, payload: &TransactionPayload) -> String {
+        format!("{}{}{}", self.normalization.normalize_field(&self.address_agent_id(&payload.agent_id)),
+                self.normalization.normalize_field(&payload.message_type),
+                self.normalization.normalize_timestamp(payload.timestamp))
+    }
+
+    pub fn explain_address(&self, payload: &TransactionPayload) -> AddressDerivation {
+        let payload_input = self.address_input_for(payload);
+        let namespace_checksum = helper::calculate_checksum_with(self.hash_algorithm, &self.name);
+        let payload_checksum = self.payload_checksum(&mut ChecksumBuilder::new(self.hash_algorithm), payload);
+        let address = self.state_address_from(&self.calculate_namespace(), &payload_checksum);
+
+        AddressDerivation {
+            namespace_input: self.name.clone(),
+            payload_input,
+            namespace_checksum,
+            payload_checksum,
+            address
+        }
+    }
+
+    fn state_address_from(&self, namespace_part: &str, payload_part: &str) -> String {
+        format!("{}{}", namespace_part, self.address_case.apply(self.address_scheme.suffix_of(payload_part)))
+    }
+
+    pub fn validate_address_scheme(&self) -> Result<(), TransactionFamilyError> {
+        // Agent prefixes only hold when the address suffix keeps the whole agent segment
+        if self.address_layout == AddressLayout::AgentPrefixed && self.address_scheme.suffix_length() < AGENT_SEGMENT_LENGTH {
+            return Err(TransactionFamilyError::InvalidAddressScheme);
+        }
+        Ok(())
+    }
+
+    pub fn validate_address_case(&self, address: &str) -> Result<(), TransactionFamilyError> {
+        if self.address_case.apply(address) == address {
+            Ok(())
+        } else {
+            Err(TransactionFamilyError::MismatchedAddressCase)
+        }
+    }
+
+    pub fn namespaces_collide(families: &[TransactionFamily]) -> Vec<(String, String)> {
+        let namespaces: Vec<String> = families.iter().map(|family| family.calculate_namespace()).collect();
+
+        let mut collisions = Vec::new();
+        for (index, family) in families.iter().enumerate() {
+            for (other_index, other_family) in families.iter().enumerate().skip(index + 1) {
+                if family.name != other_family.name && namespaces[index] == namespaces[other_index] {
+                    collisions.push((family.name.clone(), other_family.name.clone()));
+                }
+            }
+        }
+        collisions
     }
 
     pub fn latest_version(&self) -> String {
@@ -41,14 +252,75 @@ impl Default for TransactionFamily {
     fn default() -> Self {
         TransactionFamily {
             name: "".to_string(),
-            versions: Vec::new()
+            versions: Vec::new(),
+            hash_algorithm: HashAlgorithm::default(),
+            address_case: AddressCase::default(),
+            address_layout: AddressLayout::default(),
+            address_scheme: AddressScheme::default(),
+            normalization: NormalizationOptions::default(),
+            agent_id_policy: AgentIdPolicy::default()
         }
     }
 }
 
+#[derive(Default)]
+pub struct TransactionFamilyBuilder {
+    family: TransactionFamily
+}
+
+impl TransactionFamilyBuilder {
+    pub fn name(mut self, name: &str) -> Self {
+        self.family.name = name.to_string();
+        self
+    }
+
+    pub fn add_version(mut self, version: &str) -> Self {
+        self.family.versions.push(version.to_string());
+        self
+    }
+
+    pub fn hash_algorithm(mut self, hash_algorithm: HashAlgorithm) -> Self {
+        self.family.hash_algorithm = hash_algorithm;
+        self
+    }
+
+    pub fn address_case(mut self, address_case: AddressCase) -> Self {
+        self.family.address_case = address_case;
+        self
+    }
+
+    pub fn address_layout(mut self, address_layout: AddressLayout) -> Self {
+        self.family.address_layout = address_layout;
+        self
+    }
+
+    pub fn address_scheme(mut self, address_scheme: AddressScheme) -> Self {
+        self.family.address_scheme = address_scheme;
+        self
+    }
+
+    pub fn build(self) -> Result<TransactionFamily, TransactionFamilyError> {
+        if self.family.name.is_empty() {
+            return Err(TransactionFamilyError::MissingName);
+        }
+        if self.family.versions.is_empty() {
+            return Err(TransactionFamilyError::MissingVersion);
+        }
+        self.family.validate_address_scheme()?;
+
+        Ok(self.family)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::TransactionFamily;
+    use crate::{process_transaction, TransactionFamily, TransactionFamilyError};
+    use crate::helper;
+    use crate::error::CrateError;
+    use crate::messages::registry::ValidatorRegistry;
+    use crate::payloads::pipe_separated;
+    use crate::helper::{AddressCase, AddressLayout, AddressScheme, AgentIdPolicy, HashAlgorithm, NormalizationOptions,
+                        TimestampUnit};
     use crate::payloads::TransactionPayload;
 
     #[test]
@@ -91,4 +363,492 @@ mod test {
 
         assert_eq!(version, version2)
     }
+
+    #[test]
+    fn a_family_can_be_built_incrementally() {
+        let family = TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .add_version("0.2.0")
+            .build()
+            .expect("Could not build transaction family");
+
+        assert_eq!(family, TransactionFamily::new("alica_messages", &["0.1.0".to_string(), "0.2.0".to_string()]))
+    }
+
+    #[test]
+    fn a_family_without_a_name_cannot_be_built() {
+        let result = TransactionFamily::builder().add_version("0.1.0").build();
+
+        assert_eq!(result, Err(TransactionFamilyError::MissingName))
+    }
+
+    #[test]
+    fn a_family_without_a_version_cannot_be_built() {
+        let result = TransactionFamily::builder().name("alica_messages").build();
+
+        assert_eq!(result, Err(TransactionFamilyError::MissingVersion))
+    }
+
+    #[test]
+    fn the_hash_algorithm_determines_the_namespace() {
+        let family = TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .hash_algorithm(HashAlgorithm::Sha256)
+            .build()
+            .expect("Could not build transaction family");
+        let default_family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        assert_ne!(family.calculate_namespace(), default_family.calculate_namespace())
+    }
+
+    #[test]
+    fn a_valid_transaction_is_parsed_validated_and_addressed() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let role_switch = json::object!{
+            senderId: {
+                type: 0,
+                value: "id"
+            },
+            roleId: 1
+        }.dump();
+        let bytes = format!("id|RoleSwitch|{}|1", role_switch).as_bytes().to_vec();
+
+        let (payload, address) = process_transaction(&bytes, &pipe_separated::Format::default(), &family,
+                                                     &ValidatorRegistry::with_defaults())
+            .expect("Could not process transaction");
+
+        assert_eq!(payload, TransactionPayload::new("id", "RoleSwitch", role_switch.as_bytes(), 1));
+        assert_eq!(address, family.calculate_state_address_for(&payload))
+    }
+
+    #[test]
+    fn a_transaction_with_an_invalid_message_is_rejected() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let bytes = "id|RoleSwitch|{}|1".as_bytes();
+
+        let result = process_transaction(bytes, &pipe_separated::Format::default(), &family,
+                                         &ValidatorRegistry::with_defaults());
+
+        match result {
+            Err(CrateError::Validation(_)) => (),
+            _ => panic!("Expected a validation error")
+        }
+    }
+
+    #[test]
+    fn a_malformed_transaction_is_rejected() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        let result = process_transaction("id|RoleSwitch".as_bytes(), &pipe_separated::Format::default(), &family,
+                                         &ValidatorRegistry::with_defaults());
+
+        match result {
+            Err(CrateError::Payload(_)) => (),
+            _ => panic!("Expected a payload error")
+        }
+    }
+
+    #[test]
+    fn families_sharing_a_namespace_are_reported() {
+        let families = vec![
+            TransactionFamily::new("family_643", &[]),
+            TransactionFamily::new("alica_messages", &[]),
+            TransactionFamily::new("family_1986", &[]),
+        ];
+
+        let collisions = TransactionFamily::namespaces_collide(&families);
+
+        assert_eq!(collisions, vec![("family_643".to_string(), "family_1986".to_string())])
+    }
+
+    #[test]
+    fn families_with_distinct_namespaces_do_not_collide() {
+        let families = vec![
+            TransactionFamily::new("alica_messages", &[]),
+            TransactionFamily::new("other_messages", &[]),
+        ];
+
+        let collisions = TransactionFamily::namespaces_collide(&families);
+
+        assert!(collisions.is_empty())
+    }
+
+    #[test]
+    fn batch_addresses_match_individually_calculated_ones() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payloads = vec![
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+            TransactionPayload::new("other id", "SyncReady", "msg".as_bytes(), 2),
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 3),
+        ];
+
+        let addresses = family.addresses_for(&payloads);
+
+        let expected_addresses: Vec<String> = payloads.iter()
+            .map(|payload| family.calculate_state_address_for(payload))
+            .collect();
+        assert_eq!(addresses, expected_addresses)
+    }
+
+    #[test]
+    fn addresses_are_lowercase_by_default() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        let address = family.calculate_state_address_for(&TransactionPayload::default());
+
+        assert_eq!(address, address.to_lowercase());
+        assert!(family.validate_address_case(&address).is_ok());
+    }
+
+    #[test]
+    fn addresses_are_uppercase_if_configured() {
+        let family = TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .address_case(AddressCase::Upper)
+            .build()
+            .expect("Could not build transaction family");
+        let lowercase_family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        let address = family.calculate_state_address_for(&TransactionPayload::default());
+
+        assert_eq!(address, lowercase_family.calculate_state_address_for(&TransactionPayload::default()).to_uppercase());
+        assert!(address.starts_with(&family.calculate_namespace()));
+    }
+
+    #[test]
+    fn addresses_of_the_wrong_case_are_rejected() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let address = family.calculate_state_address_for(&TransactionPayload::default()).to_uppercase();
+
+        let result = family.validate_address_case(&address);
+
+        assert_eq!(result, Err(TransactionFamilyError::MismatchedAddressCase))
+    }
+
+    #[test]
+    fn the_address_explanation_matches_the_calculated_address() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payload = TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1);
+
+        let derivation = family.explain_address(&payload);
+
+        assert_eq!(derivation.address, family.calculate_state_address_for(&payload));
+        assert_eq!(derivation.namespace_input, "alica_messages");
+        assert_eq!(derivation.payload_input, "idRoleSwitch1");
+        assert_eq!(derivation.payload_checksum, helper::calculate_checksum(&"idRoleSwitch1"));
+        assert!(derivation.address.starts_with(&derivation.namespace_checksum[..6]));
+    }
+
+    #[test]
+    fn inputs_are_not_normalized_by_default() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payload = TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1);
+        let padded_payload = TransactionPayload::new(" ID ", "roleswitch", "msg".as_bytes(), 1);
+
+        assert_ne!(family.calculate_state_address_for(&payload), family.calculate_state_address_for(&padded_payload))
+    }
+
+    #[test]
+    fn normalized_inputs_ignore_whitespace_and_case() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()])
+            .with_normalization(NormalizationOptions { trim: true, lowercase: true, ..NormalizationOptions::default() });
+        let payload = TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1);
+        let padded_payload = TransactionPayload::new(" ID ", "roleswitch\t", "msg".as_bytes(), 1);
+
+        assert_eq!(family.calculate_state_address_for(&payload), family.calculate_state_address_for(&padded_payload));
+        assert_eq!(family.addresses_for(&[padded_payload]), vec![family.calculate_state_address_for(&payload)]);
+    }
+
+    #[test]
+    fn normalized_timestamps_are_canonicalized_to_seconds() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let normalizing_family = family.clone().with_normalization(NormalizationOptions {
+            timestamp_unit: TimestampUnit::Milliseconds,
+            ..NormalizationOptions::default()
+        });
+
+        let address = normalizing_family.calculate_state_address_for(&TransactionPayload::new("id", "type", &[], 1_500));
+
+        assert_eq!(address, family.calculate_state_address_for(&TransactionPayload::new("id", "type", &[], 1)))
+    }
+
+    #[test]
+    fn the_address_is_derived_from_the_address_input() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payload = TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1);
+
+        let address_input = family.address_input_for(&payload);
+
+        assert_eq!(address_input, "idRoleSwitch1");
+        assert_eq!(family.calculate_state_address_for(&payload),
+                   format!("{}{}", family.calculate_namespace(), &helper::calculate_checksum(&address_input)[..64]));
+    }
+
+    #[test]
+    fn the_fallible_namespace_matches_the_infallible_one() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        let namespace = family.try_calculate_namespace().expect("Could not calculate namespace");
+
+        assert_eq!(namespace, family.calculate_namespace())
+    }
+
+    #[test]
+    fn a_family_without_a_name_has_no_namespace() {
+        let result = TransactionFamily::default().try_calculate_namespace();
+
+        match result {
+            Err(CrateError::Family(TransactionFamilyError::MissingName)) => (),
+            _ => panic!("Expected a missing name error")
+        }
+    }
+
+    #[test]
+    fn a_checksum_shorter_than_a_namespace_is_rejected() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        let result = family.namespace_from("abc");
+
+        match result {
+            Err(CrateError::TooShort { expected: 6, actual: 3 }) => (),
+            _ => panic!("Expected a too short error")
+        }
+    }
+
+    #[test]
+    fn the_longest_address_scheme_fits_the_shortest_checksum() {
+        let family = TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .hash_algorithm(HashAlgorithm::Sha256)
+            .address_scheme(AddressScheme::new(64, 64).expect("Could not create address scheme"))
+            .build()
+            .expect("Could not build transaction family");
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+        let namespace = family.try_calculate_namespace().expect("Could not calculate namespace");
+
+        assert_eq!(namespace, family.calculate_namespace());
+        assert_eq!(namespace.len(), 64);
+        assert_eq!(family.calculate_state_address_for(&payload).len(), 128);
+    }
+
+    #[test]
+    fn the_agent_prefix_of_a_flat_layout_is_the_namespace() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        assert_eq!(family.prefix_for_agent("id"), family.calculate_namespace())
+    }
+
+    #[test]
+    fn every_address_of_an_agent_starts_with_its_prefix() {
+        let family = TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .address_layout(AddressLayout::AgentPrefixed)
+            .build()
+            .expect("Could not build transaction family");
+        let payloads = [
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "SyncReady", "msg".as_bytes(), 2),
+            TransactionPayload::new("other id", "RoleSwitch", "msg".as_bytes(), 1),
+        ];
+
+        let prefix = family.prefix_for_agent("id");
+        let addresses = family.addresses_for(&payloads);
+
+        assert_eq!(prefix.len(), 22);
+        assert!(addresses[0].starts_with(&prefix));
+        assert!(addresses[1].starts_with(&prefix));
+        assert!(!addresses[2].starts_with(&prefix));
+        assert_eq!(addresses[0], family.calculate_state_address_for(&payloads[0]));
+        assert_eq!(addresses[0].len(), 70);
+    }
+
+    #[test]
+    fn a_scheme_not_fitting_the_layout_is_rejected_when_calculating_addresses() {
+        let family = TransactionFamily {
+            address_layout: AddressLayout::AgentPrefixed,
+            address_scheme: AddressScheme::new(6, 8).expect("Could not create address scheme"),
+            ..TransactionFamily::new("alica_messages", &["0.1.0".to_string()])
+        };
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+        let result = family.try_calculate_state_address_for(&payload);
+
+        match result {
+            Err(CrateError::Family(TransactionFamilyError::InvalidAddressScheme)) => (),
+            _ => panic!("Expected an invalid address scheme error")
+        }
+    }
+
+    #[test]
+    fn an_agent_prefixed_layout_needs_a_suffix_holding_the_agent_segment() {
+        let builder = || TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .address_layout(AddressLayout::AgentPrefixed);
+
+        let short_suffix_result = builder()
+            .address_scheme(AddressScheme::new(6, 8).expect("Could not create address scheme"))
+            .build();
+        let family = builder()
+            .address_scheme(AddressScheme::new(6, 16).expect("Could not create address scheme"))
+            .build()
+            .expect("Could not build transaction family");
+
+        assert_eq!(short_suffix_result, Err(TransactionFamilyError::InvalidAddressScheme));
+        assert_eq!(family.calculate_state_address_for(&TransactionPayload::new("id", "type", &[], 1)),
+                   family.prefix_for_agent("id"));
+    }
+
+    #[test]
+    fn agent_ids_with_control_characters_are_rejected_by_default() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payload = TransactionPayload::new("a\nb", "type", &[], 1);
+
+        let result = family.try_calculate_state_address_for(&payload);
+
+        match result {
+            Err(CrateError::Family(TransactionFamilyError::InvalidAgentId)) => (),
+            _ => panic!("Expected an invalid agent id error")
+        }
+    }
+
+    #[test]
+    fn control_characters_can_be_stripped_from_agent_ids() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()])
+            .with_agent_id_policy(AgentIdPolicy::Strip);
+        let payload = TransactionPayload::new("a\nb", "type", &[], 1);
+
+        let address = family.try_calculate_state_address_for(&payload).expect("Could not calculate address");
+
+        assert_eq!(address, family.calculate_state_address_for(&TransactionPayload::new("ab", "type", &[], 1)))
+    }
+
+    #[test]
+    fn control_characters_can_be_percent_encoded_in_agent_ids() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()])
+            .with_agent_id_policy(AgentIdPolicy::PercentEncode);
+        let payload = TransactionPayload::new("a\nb", "type", &[], 1);
+
+        let address = family.try_calculate_state_address_for(&payload).expect("Could not calculate address");
+
+        assert_eq!(family.address_input_for(&payload), "a%0Abtype1");
+        assert_ne!(address, family.calculate_state_address_for(&TransactionPayload::new("a%0Ab", "type", &[], 1)));
+    }
+
+    #[test]
+    fn the_address_bytes_of_a_batch_count_each_unique_address_once() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payloads = [
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "RoleSwitch", "other msg".as_bytes(), 1),
+            TransactionPayload::new("id", "SyncReady", "msg".as_bytes(), 2),
+        ];
+
+        let address_bytes = family.address_bytes_for(&payloads);
+
+        assert_eq!(address_bytes, 2 * 70)
+    }
+
+    #[test]
+    fn a_custom_address_scheme_determines_prefix_and_address_length() {
+        let family = TransactionFamily::builder()
+            .name("alica_messages")
+            .add_version("0.1.0")
+            .address_scheme(AddressScheme::new(8, 32).expect("Could not create address scheme"))
+            .build()
+            .expect("Could not build transaction family");
+        let sawtooth_family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+        let address = family.calculate_state_address_for(&payload);
+
+        assert_eq!(address.len(), 40);
+        assert_eq!(family.calculate_namespace().len(), 8);
+        assert!(address.starts_with(&family.calculate_namespace()));
+        assert!(family.calculate_namespace().starts_with(&sawtooth_family.calculate_namespace()));
+    }
+
+    #[test]
+    fn the_default_address_scheme_is_the_sawtooth_one() {
+        let family = TransactionFamily::default();
+
+        assert_eq!(family.address_scheme, AddressScheme::sawtooth());
+        assert_eq!(family.address_scheme.address_length(), 70);
+    }
+
+    #[test]
+    fn an_address_scheme_longer_than_the_shortest_checksum_is_rejected() {
+        for (prefix_length, suffix_length) in [(6, 65), (65, 64)] {
+            let result = AddressScheme::new(prefix_length, suffix_length);
+
+            match result {
+                Err(CrateError::Family(TransactionFamilyError::InvalidAddressScheme)) => (),
+                _ => panic!("Expected an invalid address scheme error")
+            }
+        }
+    }
+
+    #[test]
+    fn payloads_differing_only_in_their_message_collide() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payloads = [
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "SyncReady", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "RoleSwitch", "other msg".as_bytes(), 1),
+        ];
+
+        let collisions = family.find_address_collisions(&payloads);
+
+        assert_eq!(collisions, vec![(0, 2)])
+    }
+
+    #[test]
+    fn a_batch_of_distinct_payloads_has_no_collisions() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payloads = [
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 2),
+            TransactionPayload::new("other id", "RoleSwitch", "msg".as_bytes(), 1),
+        ];
+
+        let collisions = family.find_address_collisions(&payloads);
+
+        assert!(collisions.is_empty())
+    }
+
+    // Addresses of the alica_messages family with default settings, computed independently of this crate as
+    // sha512(name)[..6] + sha512(agent_id + message_type + timestamp)[..64]
+    const ADDRESS_VECTORS: [(&str, &str, u64, &str); 3] = [
+        ("agent_1", "RoleSwitch", 1_600_000_000,
+         "1502a3c3e7cf2f2c03c27628227945e7934625a8a85772b1f60dcbfe8ac16368838239"),
+        ("agent_2", "SyncReady", 1_600_000_001,
+         "1502a3709950b5338849387ac98ae65adabc36dcd6f1b455cff7ed896a9b62fb1dce1b"),
+        ("7", "AlicaEngineInfo", 42,
+         "1502a34bba19e63a8eec160c96949ec9e6b670c4470614aab044ffb65bef8bf8213725"),
+    ];
+
+    #[test]
+    fn addresses_match_the_known_vectors() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+
+        for (agent_id, message_type, timestamp, expected_address) in ADDRESS_VECTORS {
+            let payload = TransactionPayload::new(agent_id, message_type, "{}".as_bytes(), timestamp);
+
+            assert!(family.matches_expected(&payload, expected_address), "Address mismatch for {}", agent_id);
+        }
+    }
+
+    #[test]
+    fn addresses_do_not_match_the_vectors_of_other_payloads() {
+        let family = TransactionFamily::new("alica_messages", &["0.1.0".to_string()]);
+        let payload = TransactionPayload::new("agent_1", "RoleSwitch", "{}".as_bytes(), 1_600_000_001);
+
+        assert!(!family.matches_expected(&payload, ADDRESS_VECTORS[0].3))
+    }
 }
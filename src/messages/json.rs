@@ -1,104 +1,452 @@
-use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationResult};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError, AlicaMessageValidationResult, Warning};
+use crate::messages::AlicaMessageValidationError::InvalidFormat;
+use crate::messages::registry::ValidatorRegistry;
+use crate::helper::error_text;
+use crate::error::ErrorReason;
 
 pub mod validation {
-    use crate::messages::AlicaMessageValidationError::{InvalidFormat, MissingField};
-    use crate::messages::json::CapnZeroIdValidator;
+    use crate::helper::error_text;
+use crate::error::ErrorReason;
+    use crate::messages::AlicaMessageValidationError::{self, InvalidFormat, MissingField};
+    use crate::messages::json::CapnZeroId;
     use crate::messages::{AlicaMessageValidationResult, AlicaMessageJsonValidator};
 
+    pub type FieldCheck = fn(&json::object::Object, &str) -> AlicaMessageValidationResult;
+
+    pub fn validate_all(container: &json::object::Object, checks: &[(&str, FieldCheck)])
+                        -> Result<(), Vec<AlicaMessageValidationError>> {
+        let errors: Vec<AlicaMessageValidationError> = checks.iter()
+            .filter_map(|(field, check)| check(container, field).err())
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    pub fn get_field<'a>(container: &'a json::object::Object, field: &str)
+                         -> Result<&'a json::JsonValue, AlicaMessageValidationError> {
+        match container.get(field) {
+            Some(json::JsonValue::Null) => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is null", field))),
+            Some(value) => Ok(value),
+            None => Err(MissingField(field.to_string()))
+        }
+    }
+
     pub fn validate_string_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
-        let value = container.get(field).ok_or_else(|| MissingField(field.to_string()))?;
-        value.as_str().ok_or_else(|| InvalidFormat(format!("{} is no string", field)))?;
+        let value = get_field(container, field)?;
+        value.as_str().ok_or_else(|| InvalidFormat(ErrorReason::WrongType, error_text!("{} is no string", field)))?;
+        Ok(())
+    }
+
+    pub fn validate_printable_string_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        let value = get_field(container, field)?
+            .as_str()
+            .ok_or_else(|| InvalidFormat(ErrorReason::WrongType, error_text!("{} is no string", field)))?;
+        if value.chars().any(char::is_control) {
+            return Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("{} contains control characters", field)));
+        }
         Ok(())
     }
 
     pub fn validate_integer_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
-        let value = container.get(field).ok_or_else(|| MissingField(field.to_string()))?;
-        value.as_i64().ok_or_else(|| InvalidFormat(format!("{} is no integer", field)))?;
+        extract_integer(container, field, false)?;
+        Ok(())
+    }
+
+    pub fn validate_lenient_integer_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        extract_integer(container, field, true)?;
+        Ok(())
+    }
+
+    pub fn extract_integer(container: &json::object::Object, field: &str, lenient: bool)
+                           -> Result<i64, AlicaMessageValidationError> {
+        let value = get_field(container, field)?;
+        let integer = match value.as_str() {
+            Some(value) if lenient => value.trim().parse::<i64>().ok(),
+            _ => value.as_i64()
+        };
+        integer.ok_or_else(|| InvalidFormat(ErrorReason::WrongType, error_text!("{} is no integer", field)))
+    }
+
+    pub fn validate_integer_enum_field(container: &json::object::Object, field: &str, allowed_values: &[i64])
+                                       -> AlicaMessageValidationResult {
+        let value = get_field(container, field)?
+            .as_i64()
+            .ok_or_else(|| InvalidFormat(ErrorReason::WrongType, error_text!("{} is no integer", field)))?;
+        if !allowed_values.contains(&value) {
+            return Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("{} has disallowed value {}", field, value)));
+        }
+        Ok(())
+    }
+
+    pub fn validate_u32_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        validate_integer_field_in_range(container, field, u32::MIN as i64, u32::MAX as i64, "u32")
+    }
+
+    pub fn validate_i32_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        validate_integer_field_in_range(container, field, i32::MIN as i64, i32::MAX as i64, "i32")
+    }
+
+    fn validate_integer_field_in_range(container: &json::object::Object, field: &str, min: i64, max: i64, range_name: &str)
+                                       -> AlicaMessageValidationResult {
+        validate_integer_field(container, field)?;
+        let value = container[field].as_i64().unwrap_or_default();
+        if value < min || value > max {
+            return Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("{} out of {} range", field, range_name)));
+        }
+        Ok(())
+    }
+
+    pub fn validate_float_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        let value = get_field(container, field)?;
+        let value = value.as_f64().ok_or_else(|| InvalidFormat(ErrorReason::WrongType, error_text!("{} is no number", field)))?;
+        if !value.is_finite() {
+            return Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("{} is not finite", field)));
+        }
         Ok(())
     }
 
     pub(crate) fn validate_boolean_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
-        let value = container.get(field).ok_or_else(|| MissingField(field.to_string()))?;
-        value.as_bool().ok_or_else(|| InvalidFormat(format!("{} is no integer", field)))?;
+        let value = get_field(container, field)?;
+        value.as_bool().ok_or_else(|| InvalidFormat(ErrorReason::WrongType, error_text!("{} is no integer", field)))?;
         Ok(())
     }
 
     pub fn validate_capnzero_id_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
-        match container.get(field) {
-            Some(id) => CapnZeroIdValidator::new().validate(id.dump().as_bytes()),
-            None => Err(MissingField(field.to_string()))
+        extract_capnzero_id(container, field)?;
+        Ok(())
+    }
+
+    pub fn extract_capnzero_id(container: &json::object::Object, field: &str)
+                               -> Result<CapnZeroId, AlicaMessageValidationError> {
+        match get_field(container, field)? {
+            json::JsonValue::Object(id) => CapnZeroId::from_json(id),
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is no object", field)))
+        }
+    }
+
+    pub const DEFAULT_MAX_LIST_ELEMENTS: usize = 1024;
+
+    pub fn extract_capnzero_id_list(container: &json::object::Object, field: &str)
+                                    -> Result<Vec<CapnZeroId>, AlicaMessageValidationError> {
+        match get_field(container, field)? {
+            json::JsonValue::Array(array_json) => {
+                array_json.iter()
+                    .map(|entry| match entry {
+                        json::JsonValue::Object(id) => CapnZeroId::from_json(id),
+                        _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} contains a non object entry", field)))
+                    })
+                    .collect()
+            },
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is no array", field)))
         }
     }
 
     pub fn validate_integer_list_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
-        match container.get(field) {
-            Some(field_json) => match field_json {
-                json::JsonValue::Array(array_json) => {
-                    array_json.iter()
-                        .map(|array_entry| match array_entry.as_i64() {
-                            Some(_) => Ok(()),
-                            None => Err(InvalidFormat(format!("{} contains a non integer entry", field)))
-                        })
-                        .collect()
-                },
-                _ => Err(InvalidFormat(format!("{} is no array", field)))
+        validate_integer_list_field_bounded(container, field, usize::MAX)
+    }
+
+    pub fn validate_integer_list_field_bounded(container: &json::object::Object, field: &str, max_elements: usize)
+                                               -> AlicaMessageValidationResult {
+        match get_field(container, field)? {
+            json::JsonValue::Array(array_json) if array_json.len() > max_elements =>
+                Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("{} has too many elements", field))),
+            json::JsonValue::Array(array_json) => {
+                array_json.iter()
+                    .try_for_each(|array_entry| match array_entry.as_i64() {
+                        Some(_) => Ok(()),
+                        None => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} contains a non integer entry", field)))
+                    })
             },
-            None => Err(MissingField(field.to_string()))
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is no array", field)))
+        }
+    }
+
+    pub fn validate_integer_list_field_is_subset_of(container: &json::object::Object, field: &str, superset_field: &str)
+                                                    -> AlicaMessageValidationResult {
+        validate_integer_list_field_bounded(container, field, DEFAULT_MAX_LIST_ELEMENTS)?;
+        validate_integer_list_field_bounded(container, superset_field, DEFAULT_MAX_LIST_ELEMENTS)?;
+
+        let superset: Vec<i64> = container[superset_field].members()
+            .filter_map(|entry| entry.as_i64())
+            .collect();
+
+        container[field].members()
+            .filter_map(|entry| entry.as_i64())
+            .try_for_each(|entry| match superset.contains(&entry) {
+                true => Ok(()),
+                false => Err(InvalidFormat(
+                    ErrorReason::DisallowedValue, error_text!("{} contains {} which is not part of {}", field, entry, superset_field)))
+            })
+    }
+
+    pub fn validate_delta_encoded_integer_list_field(container: &json::object::Object, field: &str, non_decreasing: bool)
+                                                     -> AlicaMessageValidationResult {
+        validate_integer_list_field_bounded(container, field, DEFAULT_MAX_LIST_ELEMENTS)?;
+
+        let mut previous_value: Option<i64> = None;
+        for delta in container[field].members().filter_map(|entry| entry.as_i64()) {
+            let value = previous_value.unwrap_or(0).checked_add(delta)
+                .ok_or_else(|| InvalidFormat(ErrorReason::OutOfRange, error_text!("{} overflows when decoded", field)))?;
+            if value < 0 {
+                return Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("{} decodes to the negative value {}", field, value)));
+            }
+            if non_decreasing && previous_value.is_some_and(|previous| value < previous) {
+                return Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("{} decodes to decreasing values", field)));
+            }
+            previous_value = Some(value);
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_sorted_integer_list_field(container: &json::object::Object, field: &str, ascending: bool)
+                                              -> AlicaMessageValidationResult {
+        validate_integer_list_field_bounded(container, field, DEFAULT_MAX_LIST_ELEMENTS)?;
+
+        let values: Vec<i64> = container[field].members()
+            .filter_map(|entry| entry.as_i64())
+            .collect();
+        let is_sorted = values.windows(2)
+            .all(|pair| if ascending { pair[0] <= pair[1] } else { pair[0] >= pair[1] });
+
+        match is_sorted {
+            true => Ok(()),
+            false => {
+                let order = if ascending { "ascending" } else { "descending" };
+                Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("{} is not sorted in {} order", field, order)))
+            }
+        }
+    }
+
+    pub fn validate_iso8601_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        validate_string_field(container, field)?;
+        let value = container[field].as_str().unwrap_or_default();
+        chrono::DateTime::parse_from_rfc3339(value)
+            .map_err(|_| InvalidFormat(ErrorReason::Malformed, error_text!("{} is no ISO-8601 date time", field)))?;
+        Ok(())
+    }
+
+    pub fn validate_string_map_field(container: &json::object::Object, field: &str) -> AlicaMessageValidationResult {
+        match get_field(container, field)? {
+            json::JsonValue::Object(map) if map.len() > DEFAULT_MAX_LIST_ELEMENTS =>
+                Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("{} has too many elements", field))),
+            json::JsonValue::Object(map) => {
+                map.iter()
+                    .try_for_each(|(key, value)| match value.as_str() {
+                        Some(_) => Ok(()),
+                        None => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} contains a non string value for key {}", field,
+                                                         crate::helper::truncate_for_error(key))))
+                    })
+            },
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is no object", field)))
+        }
+    }
+
+    pub fn validate_exactly_one_of(container: &json::object::Object, fields: &[&str]) -> AlicaMessageValidationResult {
+        let present_fields: Vec<&str> = fields.iter()
+            .copied()
+            .filter(|field| !matches!(container.get(field), None | Some(json::JsonValue::Null)))
+            .collect();
+
+        match present_fields.len() {
+            1 => Ok(()),
+            0 => Err(InvalidFormat(ErrorReason::Conflict, error_text!("Exactly one of {} is required", fields.join(", ")))),
+            _ => Err(InvalidFormat(ErrorReason::Conflict, error_text!("Only one of {} is allowed", present_fields.join(", "))))
+        }
+    }
+
+    pub fn validate_capnzero_id_list_field_has_consistent_lengths(container: &json::object::Object, field: &str)
+                                                                 -> AlicaMessageValidationResult {
+        let mut expected_length = None;
+        for id in extract_capnzero_id_list(container, field)? {
+            let length = data_encoding::BASE64.decode(id.value.as_bytes())
+                .map_err(|_| InvalidFormat(ErrorReason::NotBase64, error_text!("{} contains an id value which is no base64", field)))?
+                .len();
+            match expected_length {
+                Some(expected_length) if expected_length != length =>
+                    return Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("{} contains ids of different lengths", field))),
+                _ => expected_length = Some(length)
+            }
         }
+        Ok(())
+    }
+
+    pub fn validate_list_field_has_unique_components(container: &json::object::Object, field: &str,
+                                                     key: &dyn Fn(&json::JsonValue) -> Option<String>)
+                                                     -> AlicaMessageValidationResult {
+        let entries = match get_field(container, field)? {
+            json::JsonValue::Array(array_json) => array_json,
+            _ => return Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is no array", field)))
+        };
+
+        let mut seen_keys = std::collections::HashSet::new();
+        for entry_key in entries.iter().filter_map(key) {
+            if !seen_keys.insert(entry_key.clone()) {
+                return Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("{} contains {} more than once", field,
+                                                     crate::helper::truncate_for_error(&entry_key))));
+            }
+        }
+        Ok(())
     }
 
     pub fn validate_list_field_with_complex_components(container: &json::object::Object, field: &str, validator: &dyn AlicaMessageJsonValidator)
                                                        -> AlicaMessageValidationResult {
-        match container.get(field) {
-            Some(field_json) => match field_json {
-                json::JsonValue::Array(array_json) => {
-                    array_json.iter()
-                        .map(|array_entry| validator.validate(array_entry.dump().as_bytes()))
-                        .collect()
-                },
-                _ => Err(InvalidFormat(format!("{} is no array", field)))
+        validate_list_field_with_complex_components_bounded(container, field, validator, usize::MAX)
+    }
+
+    pub fn validate_list_field_with_complex_components_bounded(container: &json::object::Object, field: &str,
+                                                               validator: &dyn AlicaMessageJsonValidator,
+                                                               max_elements: usize) -> AlicaMessageValidationResult {
+        match get_field(container, field)? {
+            json::JsonValue::Array(array_json) if array_json.len() > max_elements =>
+                Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("{} has too many elements", field))),
+            json::JsonValue::Array(array_json) => {
+                array_json.iter()
+                    .try_for_each(|array_entry| match array_entry {
+                        json::JsonValue::Object(entry) => validator.validate_object(entry),
+                        _ => validator.validate(array_entry.dump().as_bytes())
+                    })
             },
-            None => Err(MissingField(field.to_string()))
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("{} is no array", field)))
         }
     }
 }
 
 pub mod helper {
+    use crate::helper::error_text;
+use crate::error::ErrorReason;
     use crate::messages::AlicaMessageValidationError::{self, InvalidFormat};
 
+    const UTF8_BYTE_ORDER_MARK: &[u8] = &[0xef, 0xbb, 0xbf];
+
     pub fn parse_object(data: &[u8]) -> Result<json::object::Object, AlicaMessageValidationError> {
+        let data = data.strip_prefix(UTF8_BYTE_ORDER_MARK).unwrap_or(data);
         let raw_message = String::from_utf8(data.to_vec())
-            .map_err(|_| InvalidFormat("Message is no UTF-8 string".to_string()))?;
+            .map_err(|_| InvalidFormat(ErrorReason::Malformed, error_text!("Message is no UTF-8 string")))?;
 
         let root_value = json::parse(&raw_message)
-            .map_err(|_| InvalidFormat("Message is no JSON structure".to_string()))?;
+            .map_err(|_| InvalidFormat(ErrorReason::Malformed, error_text!("Message is no JSON structure")))?;
 
         match root_value {
             json::JsonValue::Object(root_object) => Ok(root_object),
-            _ => Err(InvalidFormat("Root of message is no object".to_string()))
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("Root of message is no object")))
+        }
+    }
+
+    pub fn parse_non_empty_object(data: &[u8]) -> Result<json::object::Object, AlicaMessageValidationError> {
+        let root_object = parse_object(data)?;
+        ensure_non_empty(&root_object)?;
+        Ok(root_object)
+    }
+
+    pub fn ensure_non_empty(object: &json::object::Object) -> Result<(), AlicaMessageValidationError> {
+        if object.is_empty() {
+            return Err(InvalidFormat(ErrorReason::Malformed, error_text!("message object is empty")));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_serde_value(value: &serde_json::Value) -> json::JsonValue {
+        match value {
+            serde_json::Value::Null => json::JsonValue::Null,
+            serde_json::Value::Bool(boolean) => json::JsonValue::Boolean(*boolean),
+            serde_json::Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+                (Some(unsigned), _) => unsigned.into(),
+                (None, Some(signed)) => signed.into(),
+                (None, None) => number.as_f64().unwrap_or_default().into()
+            },
+            serde_json::Value::String(string) => string.as_str().into(),
+            serde_json::Value::Array(array) => json::JsonValue::Array(array.iter().map(from_serde_value).collect()),
+            serde_json::Value::Object(object) => {
+                let mut converted = json::object::Object::with_capacity(object.len());
+                object.iter().for_each(|(key, value)| converted.insert(key, from_serde_value(value)));
+                json::JsonValue::Object(converted)
+            }
+        }
+    }
+
+    pub fn parse_array(data: &[u8]) -> Result<Vec<json::JsonValue>, AlicaMessageValidationError> {
+        let data = data.strip_prefix(UTF8_BYTE_ORDER_MARK).unwrap_or(data);
+        let raw_message = std::str::from_utf8(data)
+            .map_err(|_| InvalidFormat(ErrorReason::Malformed, error_text!("Message is no UTF-8 string")))?;
+
+        match json::parse(raw_message) {
+            Ok(json::JsonValue::Array(root_array)) => Ok(root_array),
+            Ok(_) => Err(InvalidFormat(ErrorReason::WrongType, error_text!("Root of message is no array"))),
+            Err(_) => Err(InvalidFormat(ErrorReason::Malformed, error_text!("Message is no JSON structure")))
         }
     }
 }
 
-pub struct AlicaEngineInfoValidator {}
+pub struct AlicaEngineInfoValidator {
+    check_id_length_consistency: bool,
+    check_id_uniqueness: bool
+}
 
 impl AlicaEngineInfoValidator {
     pub fn new() -> Self {
-        AlicaEngineInfoValidator {}
+        AlicaEngineInfoValidator {
+            check_id_length_consistency: false,
+            check_id_uniqueness: false
+        }
+    }
+
+    pub fn with_id_length_consistency_check(mut self) -> Self {
+        self.check_id_length_consistency = true;
+        self
+    }
+
+    pub fn with_id_uniqueness_check(mut self) -> Self {
+        self.check_id_uniqueness = true;
+        self
     }
 }
 
 impl AlicaMessageJsonValidator for AlicaEngineInfoValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let engine_info_root = helper::parse_object(message)?;
+        self.validate_all(message).map_err(|mut errors| errors.remove(0))
+    }
 
-        validation::validate_capnzero_id_field(&engine_info_root, "senderId")?;
-        validation::validate_string_field(&engine_info_root, "masterPlan")?;
-        validation::validate_string_field(&engine_info_root, "currentPlan")?;
-        validation::validate_string_field(&engine_info_root, "currentState")?;
-        validation::validate_string_field(&engine_info_root, "currentRole")?;
-        validation::validate_string_field(&engine_info_root, "currentTask")?;
-        validation::validate_list_field_with_complex_components(&engine_info_root, "agentIdsWithMe", &CapnZeroIdValidator::new())?;
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        self.validate_all_fields(message).map_err(|mut errors| errors.remove(0))
+    }
+
+    fn validate_all(&self, message: &[u8]) -> Result<(), Vec<AlicaMessageValidationError>> {
+        self.validate_all_fields(&helper::parse_object(message).map_err(|error| vec![error])?)
+    }
+}
+
+impl AlicaEngineInfoValidator {
+    fn validate_all_fields(&self, engine_info_root: &json::object::Object) -> Result<(), Vec<AlicaMessageValidationError>> {
+        helper::ensure_non_empty(engine_info_root).map_err(|error| vec![error])?;
+
+        validation::validate_all(engine_info_root, &[
+            ("senderId", validation::validate_capnzero_id_field),
+            ("masterPlan", validation::validate_printable_string_field),
+            ("currentPlan", validation::validate_printable_string_field),
+            ("currentState", validation::validate_printable_string_field),
+            ("currentRole", validation::validate_printable_string_field),
+            ("currentTask", validation::validate_printable_string_field),
+            ("agentIdsWithMe", |container, field| {
+                validation::validate_list_field_with_complex_components_bounded(container, field, &CapnZeroIdValidator::new(),
+                                                                                validation::DEFAULT_MAX_LIST_ELEMENTS)
+            }),
+        ])?;
+        if self.check_id_length_consistency {
+            validation::validate_capnzero_id_list_field_has_consistent_lengths(engine_info_root, "agentIdsWithMe")
+                .map_err(|error| vec![error])?;
+        }
+        if self.check_id_uniqueness {
+            validation::validate_list_field_has_unique_components(engine_info_root, "agentIdsWithMe", &|agent_id| {
+                agent_id["value"].as_str().map(|value| value.to_string())
+            }).map_err(|error| vec![error])?;
+        }
 
         Ok(())
     }
@@ -114,14 +462,19 @@ impl AllocationAuthorityInfoValidator {
 
 impl AlicaMessageJsonValidator for AllocationAuthorityInfoValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let allocation_authority_info_root = helper::parse_object(message)?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, allocation_authority_info_root: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(allocation_authority_info_root)?;
 
-        validation::validate_capnzero_id_field(&allocation_authority_info_root, "senderId")?;
-        validation::validate_integer_field(&allocation_authority_info_root, "planId")?;
-        validation::validate_integer_field(&allocation_authority_info_root, "parentState")?;
-        validation::validate_integer_field(&allocation_authority_info_root, "planType")?;
-        validation::validate_capnzero_id_field(&allocation_authority_info_root, "authority")?;
-        validation::validate_list_field_with_complex_components(&allocation_authority_info_root, "entrypointRobots", &EntryPointRobotValidator::new())?;
+        validation::validate_capnzero_id_field(allocation_authority_info_root, "senderId")?;
+        validation::validate_integer_field(allocation_authority_info_root, "planId")?;
+        validation::validate_integer_field(allocation_authority_info_root, "parentState")?;
+        validation::validate_integer_field(allocation_authority_info_root, "planType")?;
+        validation::validate_capnzero_id_field(allocation_authority_info_root, "authority")?;
+        validation::validate_list_field_with_complex_components_bounded(allocation_authority_info_root, "entrypointRobots", &EntryPointRobotValidator::new(),
+                                                                        validation::DEFAULT_MAX_LIST_ELEMENTS)?;
 
         Ok(())
     }
@@ -137,44 +490,104 @@ impl EntryPointRobotValidator {
 
 impl AlicaMessageJsonValidator for EntryPointRobotValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let entry_point_robot = helper::parse_object(message)?;
-        validation::validate_integer_field(&entry_point_robot, "entrypoint")?;
-        validation::validate_list_field_with_complex_components(&entry_point_robot, "robots", &CapnZeroIdValidator::new())?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, entry_point_robot: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(entry_point_robot)?;
+        validation::validate_integer_field(entry_point_robot, "entrypoint")?;
+        validation::validate_list_field_with_complex_components_bounded(entry_point_robot, "robots", &CapnZeroIdValidator::new(),
+                                                                        validation::DEFAULT_MAX_LIST_ELEMENTS)?;
         Ok(())
     }
 }
 
-pub struct PlanTreeInfoValidator {}
+pub struct PlanTreeInfoValidator {
+    check_succeeded_eps_consistency: bool
+}
 
 impl PlanTreeInfoValidator {
     pub fn new() -> Self {
-        PlanTreeInfoValidator {}
+        PlanTreeInfoValidator {
+            check_succeeded_eps_consistency: false
+        }
+    }
+
+    pub fn with_succeeded_eps_consistency_check(mut self) -> Self {
+        self.check_succeeded_eps_consistency = true;
+        self
     }
 }
 
 impl AlicaMessageJsonValidator for PlanTreeInfoValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let plan_tree_info = helper::parse_object(message)?;
-        validation::validate_capnzero_id_field(&plan_tree_info, "senderId")?;
-        validation::validate_integer_list_field(&plan_tree_info, "stateIds")?;
-        validation::validate_integer_list_field(&plan_tree_info, "succeededEps")?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, plan_tree_info: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(plan_tree_info)?;
+        validation::validate_capnzero_id_field(plan_tree_info, "senderId")?;
+        validation::validate_integer_list_field_bounded(plan_tree_info, "stateIds", validation::DEFAULT_MAX_LIST_ELEMENTS)?;
+        validation::validate_integer_list_field_bounded(plan_tree_info, "succeededEps", validation::DEFAULT_MAX_LIST_ELEMENTS)?;
+        if self.check_succeeded_eps_consistency {
+            validation::validate_integer_list_field_is_subset_of(plan_tree_info, "succeededEps", "stateIds")?;
+        }
         Ok(())
     }
 }
 
-pub struct RoleSwitchValidator {}
+pub struct RoleSwitchValidator {
+    validator: WithSenderId<RoleIdValidator>
+}
 
 impl RoleSwitchValidator {
     pub fn new() -> Self {
-        RoleSwitchValidator {}
+        RoleSwitchValidator {
+            validator: WithSenderId::new(RoleIdValidator {})
+        }
     }
 }
 
 impl AlicaMessageJsonValidator for RoleSwitchValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let role_switch = helper::parse_object(message)?;
-        validation::validate_capnzero_id_field(&role_switch, "senderId")?;
-        validation::validate_integer_field(&role_switch, "roleId")?;
+        self.validator.validate(message)
+    }
+
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        self.validator.validate_object(message)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct RoleSwitch {
+    pub sender_id: CapnZeroId,
+    pub role_id: i64
+}
+
+impl RoleSwitch {
+    pub fn from_json(message: &[u8]) -> Result<Self, AlicaMessageValidationError> {
+        let role_switch = helper::parse_non_empty_object(message)?;
+        let sender_id = validation::extract_capnzero_id(&role_switch, "senderId")?;
+        validation::validate_u32_field(&role_switch, "roleId")?;
+
+        Ok(RoleSwitch {
+            sender_id,
+            role_id: role_switch["roleId"].as_i64().unwrap_or_default()
+        })
+    }
+}
+
+#[derive(Default)]
+struct RoleIdValidator {}
+
+impl AlicaMessageJsonValidator for RoleIdValidator {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, role_switch: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(role_switch)?;
+        validation::validate_u32_field(role_switch, "roleId")?;
         Ok(())
     }
 }
@@ -189,9 +602,14 @@ impl SolverResultValidator {
 
 impl AlicaMessageJsonValidator for SolverResultValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let solver_result = helper::parse_object(message)?;
-        validation::validate_capnzero_id_field(&solver_result, "senderId")?;
-        validation::validate_list_field_with_complex_components(&solver_result, "vars", &SolverVarValidator::new())?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, solver_result: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(solver_result)?;
+        validation::validate_capnzero_id_field(solver_result, "senderId")?;
+        validation::validate_list_field_with_complex_components_bounded(solver_result, "vars", &SolverVarValidator::new(),
+                                                                        validation::DEFAULT_MAX_LIST_ELEMENTS)?;
         Ok(())
     }
 }
@@ -206,9 +624,13 @@ impl SolverVarValidator {
 
 impl AlicaMessageJsonValidator for SolverVarValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let solver_var = helper::parse_object(message)?;
-        validation::validate_integer_field(&solver_var, "id")?;
-        validation::validate_integer_list_field(&solver_var, "value")?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, solver_var: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(solver_var)?;
+        validation::validate_integer_field(solver_var, "id")?;
+        validation::validate_integer_list_field_bounded(solver_var, "value", validation::DEFAULT_MAX_LIST_ELEMENTS)?;
         Ok(())
     }
 }
@@ -223,9 +645,13 @@ impl SyncReadyValidator {
 
 impl AlicaMessageJsonValidator for SyncReadyValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let sync_ready = helper::parse_object(message)?;
-        validation::validate_capnzero_id_field(&sync_ready, "senderId")?;
-        validation::validate_integer_field(&sync_ready, "synchronisationId")?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, sync_ready: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(sync_ready)?;
+        validation::validate_capnzero_id_field(sync_ready, "senderId")?;
+        validation::validate_integer_field(sync_ready, "synchronisationId")?;
         Ok(())
     }
 }
@@ -240,9 +666,14 @@ impl SyncTalkValidator {
 
 impl AlicaMessageJsonValidator for SyncTalkValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let sync_talk = helper::parse_object(message)?;
-        validation::validate_capnzero_id_field(&sync_talk, "senderId")?;
-        validation::validate_list_field_with_complex_components(&sync_talk, "syncData", &SyncDataValidator::new())?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, sync_talk: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(sync_talk)?;
+        validation::validate_capnzero_id_field(sync_talk, "senderId")?;
+        validation::validate_list_field_with_complex_components_bounded(sync_talk, "syncData", &SyncDataValidator::new(),
+                                                                        validation::DEFAULT_MAX_LIST_ELEMENTS)?;
         Ok(())
     }
 }
@@ -257,229 +688,2355 @@ impl SyncDataValidator {
 
 impl AlicaMessageJsonValidator for SyncDataValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let sync_data = helper::parse_object(message)?;
-        validation::validate_capnzero_id_field(&sync_data, "robotId")?;
-        validation::validate_integer_field(&sync_data, "transitionId")?;
-        validation::validate_boolean_field(&sync_data, "transitionHolds")?;
-        validation::validate_boolean_field(&sync_data, "ack")?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        SyncData::from_object(message)?;
         Ok(())
     }
 }
 
-pub struct CapnZeroIdValidator {}
+#[derive(PartialEq, Debug, Clone)]
+pub struct SyncData {
+    pub robot_id: CapnZeroId,
+    pub transition_id: i64,
+    pub transition_holds: bool,
+    pub ack: bool
+}
 
-impl CapnZeroIdValidator {
+impl SyncData {
+    pub fn from_json(message: &[u8]) -> Result<Self, AlicaMessageValidationError> {
+        SyncData::from_object(&helper::parse_object(message)?)
+    }
+
+    pub fn from_object(sync_data: &json::object::Object) -> Result<Self, AlicaMessageValidationError> {
+        helper::ensure_non_empty(sync_data)?;
+        let robot_id = validation::extract_capnzero_id(sync_data, "robotId")?;
+        validation::validate_integer_field(sync_data, "transitionId")?;
+        validation::validate_boolean_field(sync_data, "transitionHolds")?;
+        validation::validate_boolean_field(sync_data, "ack")?;
+
+        Ok(SyncData {
+            robot_id,
+            transition_id: sync_data["transitionId"].as_i64().unwrap_or_default(),
+            transition_holds: sync_data["transitionHolds"].as_bool().unwrap_or_default(),
+            ack: sync_data["ack"].as_bool().unwrap_or_default()
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct PoseValidator {}
+
+impl PoseValidator {
     pub fn new() -> Self {
-        CapnZeroIdValidator {}
+        PoseValidator {}
     }
 }
 
-impl AlicaMessageJsonValidator for CapnZeroIdValidator {
+impl AlicaMessageJsonValidator for PoseValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
-        let capnzero_id_root = helper::parse_object(message)?;
-
-        validation::validate_integer_field(&capnzero_id_root, "type")?;
-        validation::validate_string_field(&capnzero_id_root, "value")?;
+        self.validate_object(&helper::parse_object(message)?)
+    }
 
+    fn validate_object(&self, pose: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(pose)?;
+        validation::validate_float_field(pose, "x")?;
+        validation::validate_float_field(pose, "y")?;
+        validation::validate_float_field(pose, "theta")?;
+        if pose.get("frame").is_some() {
+            validation::validate_string_field(pose, "frame")?;
+        }
         Ok(())
     }
 }
 
-#[cfg(test)]
-mod test {
-    mod alica_engine_info {
-        use crate::messages::json::AlicaEngineInfoValidator;
-        use crate::messages::AlicaMessageJsonValidator;
+#[derive(Default)]
+pub struct BidValidator {}
 
-        #[test]
-        fn it_considers_a_complete_alica_engine_info_valid() {
-            let engine_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                },
-                masterPlan: "master plan",
-                currentPlan: "current plan",
-                currentState: "current state",
-                currentRole: "current role",
-                currentTask: "current task",
-                agentIdsWithMe: [
-                    {
-                        type: 1,
-                        value: "other agent"
-                    },
-                    {
-                        type: 1,
-                        value: "other other agent"
-                    },
-                ]
-            }.dump();
+impl BidValidator {
+    pub fn new() -> Self {
+        BidValidator {}
+    }
+}
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+impl AlicaMessageJsonValidator for BidValidator {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
 
-            assert!(validation_result.is_ok())
-        }
+    fn validate_object(&self, bid: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(bid)?;
+        validation::validate_capnzero_id_field(bid, "senderId")?;
+        validation::validate_integer_field(bid, "taskId")?;
+        validation::validate_float_field(bid, "utility")?;
+        Ok(())
+    }
+}
 
-        #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum CapnZeroIdType {
+    Uuid,
+    Integer,
+    Wildcard
+}
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(&message);
+impl CapnZeroIdType {
+    pub fn from_i64(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(CapnZeroIdType::Uuid),
+            1 => Some(CapnZeroIdType::Integer),
+            2 => Some(CapnZeroIdType::Wildcard),
+            _ => None
+        }
+    }
 
-            assert!(validation_result.is_err())
+    pub fn as_i64(&self) -> i64 {
+        match self {
+            CapnZeroIdType::Uuid => 0,
+            CapnZeroIdType::Integer => 1,
+            CapnZeroIdType::Wildcard => 2
         }
+    }
+}
 
-        #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CapnZeroIdValueEncoding {
+    Raw,
+    Base64,
+    Integer
+}
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(message.as_bytes());
+impl CapnZeroIdValueEncoding {
+    pub fn matches(&self, value: &str) -> bool {
+        match self {
+            CapnZeroIdValueEncoding::Raw => true,
+            CapnZeroIdValueEncoding::Base64 => data_encoding::BASE64.decode(value.as_bytes()).is_ok(),
+            CapnZeroIdValueEncoding::Integer => value.parse::<i64>().is_ok()
+        }
+    }
+}
 
-            assert!(validation_result.is_err())
+pub struct CapnZeroIdValidator {
+    value_encodings: HashMap<CapnZeroIdType, CapnZeroIdValueEncoding>
+}
+
+impl CapnZeroIdValidator {
+    pub fn new() -> Self {
+        CapnZeroIdValidator {
+            value_encodings: HashMap::new()
         }
+    }
 
-        #[test]
-        fn it_considers_an_alica_engine_info_with_missing_sender_id_invalid() {
-            let engine_info = json::object!{}.dump();
+    pub fn with_value_encoding(mut self, id_type: CapnZeroIdType, encoding: CapnZeroIdValueEncoding) -> Self {
+        self.value_encodings.insert(id_type, encoding);
+        self
+    }
+}
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+impl AlicaMessageJsonValidator for CapnZeroIdValidator {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
 
-            assert!(validation_result.is_err())
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(message)?;
+        let id = CapnZeroId::from_json(message)?;
+        match self.value_encodings.get(&id.id_type) {
+            Some(encoding) if !encoding.matches(&id.value) =>
+                Err(InvalidFormat(ErrorReason::DisallowedValue, error_text!("value does not match the encoding of type {}", id.id_type.as_i64()))),
+            _ => Ok(())
         }
+    }
+}
 
-        #[test]
-        fn it_considers_an_alica_engine_info_with_missing_master_plan_invalid() {
-            let engine_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                }
-            }.dump();
+#[derive(PartialEq, Debug, Clone)]
+pub struct CapnZeroId {
+    pub id_type: CapnZeroIdType,
+    pub value: String
+}
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+impl CapnZeroId {
+    pub fn from_json(container: &json::object::Object) -> Result<Self, AlicaMessageValidationError> {
+        validation::validate_integer_field(container, "type")?;
+        let id_type = container["type"].as_i64().unwrap_or_default();
+        let id_type = CapnZeroIdType::from_i64(id_type)
+            .ok_or_else(|| InvalidFormat(ErrorReason::DisallowedValue, error_text!("type {} is no known capnzero id type", id_type)))?;
+        validation::validate_string_field(container, "value")?;
+
+        Ok(CapnZeroId {
+            id_type,
+            value: container["value"].as_str().unwrap_or_default().to_string()
+        })
+    }
 
-            assert!(validation_result.is_err())
+    pub fn parse(message: &[u8]) -> Result<Self, AlicaMessageValidationError> {
+        CapnZeroId::from_json(&helper::parse_non_empty_object(message)?)
+    }
+}
+
+#[derive(Default)]
+pub struct FieldAliasMap {
+    aliases: HashMap<String, String>
+}
+
+impl FieldAliasMap {
+    pub fn new() -> Self {
+        FieldAliasMap {
+            aliases: HashMap::new()
+        }
+    }
+
+    pub fn with_alias(mut self, field: &str, alias: &str) -> Self {
+        self.aliases.insert(alias.to_string(), field.to_string());
+        self
+    }
+
+    pub fn resolve<'a>(&'a self, field: &'a str) -> &'a str {
+        self.aliases.get(field).map(|canonical_field| canonical_field.as_str()).unwrap_or(field)
+    }
+
+    fn canonicalize(&self, object: &json::object::Object) -> Result<json::object::Object, AlicaMessageValidationError> {
+        let mut canonical_object = json::object::Object::with_capacity(object.len());
+        for (field, field_value) in object.iter() {
+            let canonical_field = self.resolve(field);
+            if canonical_object.get(canonical_field).is_some() {
+                return Err(InvalidFormat(ErrorReason::Conflict, error_text!("{} is present more than once under its aliases", canonical_field)));
+            }
+            canonical_object.insert(canonical_field, field_value.clone());
+        }
+        Ok(canonical_object)
+    }
+}
+
+pub struct AliasedValidator<V: AlicaMessageJsonValidator> {
+    alias_map: FieldAliasMap,
+    validator: V
+}
+
+impl<V: AlicaMessageJsonValidator> AliasedValidator<V> {
+    pub fn new(alias_map: FieldAliasMap, validator: V) -> Self {
+        AliasedValidator {
+            alias_map,
+            validator
+        }
+    }
+}
+
+impl<V: AlicaMessageJsonValidator> AlicaMessageJsonValidator for AliasedValidator<V> {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        self.validator.validate_object(&self.alias_map.canonicalize(message)?)
+    }
+}
+
+#[derive(Default)]
+pub struct WithSenderId<V: AlicaMessageJsonValidator> {
+    validator: V
+}
+
+impl<V: AlicaMessageJsonValidator> WithSenderId<V> {
+    pub fn new(validator: V) -> Self {
+        WithSenderId {
+            validator
+        }
+    }
+}
+
+impl<V: AlicaMessageJsonValidator> AlicaMessageJsonValidator for WithSenderId<V> {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(message)?;
+        match validation::get_field(message, "senderId")? {
+            json::JsonValue::Object(sender_id) => CapnZeroIdValidator::new().validate_object(sender_id)?,
+            _ => return Err(InvalidFormat(ErrorReason::WrongType, error_text!("senderId is no object")))
+        }
+        self.validator.validate_object(message)
+    }
+}
+
+/// The `RecursivePlanTreeValidator` is not listed: every flat plan tree info is also a valid plan tree,
+/// so listing both would make `classify` report two message types for the same message. Use it in
+/// place of the `PlanTreeInfoValidator` when nested plan trees are expected.
+pub fn all_validators() -> Vec<(&'static str, Box<dyn AlicaMessageJsonValidator>)> {
+    vec![
+        ("AlicaEngineInfo", Box::new(AlicaEngineInfoValidator::new())),
+        ("AllocationAuthorityInfo", Box::new(AllocationAuthorityInfoValidator::new())),
+        ("EntryPointRobot", Box::new(EntryPointRobotValidator::new())),
+        ("PlanTreeInfo", Box::new(PlanTreeInfoValidator::new())),
+        ("RoleSwitch", Box::new(RoleSwitchValidator::new())),
+        ("SolverResult", Box::new(SolverResultValidator::new())),
+        ("SolverVar", Box::new(SolverVarValidator::new())),
+        ("SyncReady", Box::new(SyncReadyValidator::new())),
+        ("SyncTalk", Box::new(SyncTalkValidator::new())),
+        ("SyncData", Box::new(SyncDataValidator::new())),
+        ("CapnZeroId", Box::new(CapnZeroIdValidator::new())),
+        ("Bid", Box::new(BidValidator::new())),
+        ("Pose", Box::new(PoseValidator::new())),
+    ]
+}
+
+pub fn classify(message: &[u8]) -> Vec<(&'static str, AlicaMessageValidationResult)> {
+    all_validators().into_iter()
+        .map(|(message_type, validator)| (message_type, validator.validate(message)))
+        .collect()
+}
+
+pub fn validate_and_canonicalize(message_type: &str, message: &[u8], registry: &ValidatorRegistry)
+                                 -> Result<Vec<u8>, AlicaMessageValidationError> {
+    registry.validate(message_type, message)?;
+    let root = json::JsonValue::Object(helper::parse_object(message)?);
+    Ok(crate::helper::canonical_json(&root).into_bytes())
+}
+
+pub struct UnknownFieldsValidator<V: AlicaMessageJsonValidator> {
+    validator: V,
+    known_fields: Vec<String>,
+    strict: bool
+}
+
+impl<V: AlicaMessageJsonValidator> UnknownFieldsValidator<V> {
+    pub fn new(validator: V, known_fields: &[&str]) -> Self {
+        UnknownFieldsValidator {
+            validator,
+            known_fields: known_fields.iter().map(|field| field.to_string()).collect(),
+            strict: false
+        }
+    }
+
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+}
+
+impl<V: AlicaMessageJsonValidator> AlicaMessageJsonValidator for UnknownFieldsValidator<V> {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_with_warnings(message).map(|_| ())
+    }
+
+    fn validate_with_warnings(&self, message: &[u8]) -> Result<Vec<Warning>, AlicaMessageValidationError> {
+        let mut warnings = self.validator.validate_with_warnings(message)?;
+        let root = helper::parse_object(message)?;
+        warnings.extend(root.iter()
+            .filter(|(field, _)| !self.known_fields.iter().any(|known_field| known_field == field))
+            .map(|(field, _)| Warning::UnknownField(field.to_string())));
+
+        match warnings.first() {
+            Some(warning) if self.strict => Err(InvalidFormat(ErrorReason::UnknownField, error_text!("{}", warning))),
+            _ => Ok(warnings)
+        }
+    }
+}
+
+pub struct ClosureValidator<F>
+    where F: Fn(&[u8]) -> AlicaMessageValidationResult {
+    validate: F
+}
+
+impl<F> ClosureValidator<F>
+    where F: Fn(&[u8]) -> AlicaMessageValidationResult {
+    pub fn new(validate: F) -> Self {
+        ClosureValidator {
+            validate
+        }
+    }
+}
+
+impl<F> AlicaMessageJsonValidator for ClosureValidator<F>
+    where F: Fn(&[u8]) -> AlicaMessageValidationResult {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        (self.validate)(message)
+    }
+}
+
+pub struct ShadowValidator<V, F>
+    where V: AlicaMessageJsonValidator, F: Fn(&[u8], &AlicaMessageValidationError) {
+    validator: V,
+    on_failure: F
+}
+
+impl<V, F> ShadowValidator<V, F>
+    where V: AlicaMessageJsonValidator, F: Fn(&[u8], &AlicaMessageValidationError) {
+    pub fn new(validator: V, on_failure: F) -> Self {
+        ShadowValidator {
+            validator,
+            on_failure
+        }
+    }
+}
+
+impl<V, F> AlicaMessageJsonValidator for ShadowValidator<V, F>
+    where V: AlicaMessageJsonValidator, F: Fn(&[u8], &AlicaMessageValidationError) {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        if let Err(error) = self.validator.validate(message) {
+            (self.on_failure)(message, &error);
+        }
+        Ok(())
+    }
+}
+
+pub struct ArrayRootValidator<V: AlicaMessageJsonValidator> {
+    entry_validator: V
+}
+
+impl<V: AlicaMessageJsonValidator> ArrayRootValidator<V> {
+    pub fn new(entry_validator: V) -> Self {
+        ArrayRootValidator {
+            entry_validator
+        }
+    }
+}
+
+impl<V: AlicaMessageJsonValidator> AlicaMessageJsonValidator for ArrayRootValidator<V> {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        helper::parse_array(message)?.iter()
+            .try_for_each(|entry| self.entry_validator.validate(entry.dump().as_bytes()))
+    }
+}
+
+pub const SCHEMA_VERSION_FIELD: &str = "schemaVersion";
+
+pub struct VersionedValidator {
+    validators: BTreeMap<i64, Box<dyn AlicaMessageJsonValidator>>,
+    default_version: i64
+}
+
+impl VersionedValidator {
+    pub fn new(default_version: i64) -> Self {
+        VersionedValidator {
+            validators: BTreeMap::new(),
+            default_version
+        }
+    }
+
+    pub fn with_version(mut self, version: i64, validator: Box<dyn AlicaMessageJsonValidator>) -> Self {
+        self.validators.insert(version, validator);
+        self
+    }
+}
+
+impl AlicaMessageJsonValidator for VersionedValidator {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        let root = helper::parse_object(message)?;
+        let version = match root.get(SCHEMA_VERSION_FIELD) {
+            None => self.default_version,
+            Some(_) => validation::extract_integer(&root, SCHEMA_VERSION_FIELD, false)?
+        };
+
+        match self.validators.get(&version) {
+            Some(validator) => validator.validate(message),
+            None => Err(InvalidFormat(ErrorReason::Unsupported, error_text!("{} {} is not supported", SCHEMA_VERSION_FIELD, version)))
+        }
+    }
+}
+
+pub const DEFAULT_MAX_PLAN_TREE_DEPTH: usize = 16;
+
+pub struct RecursivePlanTreeValidator {
+    max_depth: usize
+}
+
+impl RecursivePlanTreeValidator {
+    pub fn new() -> Self {
+        RecursivePlanTreeValidator {
+            max_depth: DEFAULT_MAX_PLAN_TREE_DEPTH
+        }
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    fn validate_node(&self, node: &json::object::Object, depth: usize) -> AlicaMessageValidationResult {
+        if depth > self.max_depth {
+            return Err(InvalidFormat(ErrorReason::OutOfRange, error_text!("Plan tree exceeds the maximum depth of {}", self.max_depth)));
+        }
+
+        validation::validate_integer_list_field_bounded(node, "stateIds", validation::DEFAULT_MAX_LIST_ELEMENTS)?;
+        validation::validate_integer_list_field_bounded(node, "succeededEps", validation::DEFAULT_MAX_LIST_ELEMENTS)?;
+        if node.get("children").is_none() {
+            return Ok(());
+        }
+
+        match validation::get_field(node, "children")? {
+            json::JsonValue::Array(children) => children.iter()
+                .try_for_each(|child| match child {
+                    json::JsonValue::Object(child) => self.validate_node(child, depth + 1),
+                    _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("children contains a non object entry")))
+                }),
+            _ => Err(InvalidFormat(ErrorReason::WrongType, error_text!("children is no array")))
+        }
+    }
+}
+
+impl Default for RecursivePlanTreeValidator {
+    fn default() -> Self {
+        RecursivePlanTreeValidator::new()
+    }
+}
+
+impl AlicaMessageJsonValidator for RecursivePlanTreeValidator {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, plan_tree: &json::object::Object) -> AlicaMessageValidationResult {
+        helper::ensure_non_empty(plan_tree)?;
+        validation::validate_capnzero_id_field(plan_tree, "senderId")?;
+        self.validate_node(plan_tree, 1)
+    }
+}
+
+#[cfg(feature = "timing")]
+pub const TIMING_BUCKET_UPPER_BOUNDS: [std::time::Duration; 7] = [
+    std::time::Duration::from_micros(1),
+    std::time::Duration::from_micros(10),
+    std::time::Duration::from_micros(100),
+    std::time::Duration::from_millis(1),
+    std::time::Duration::from_millis(10),
+    std::time::Duration::from_millis(100),
+    std::time::Duration::from_secs(1),
+];
+
+/// Counts validation durations per bucket of `TIMING_BUCKET_UPPER_BOUNDS`, the last count holds
+/// every duration above the largest bound.
+#[cfg(feature = "timing")]
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct DurationHistogram {
+    counts: [u64; TIMING_BUCKET_UPPER_BOUNDS.len() + 1]
+}
+
+#[cfg(feature = "timing")]
+impl DurationHistogram {
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    fn record(&mut self, duration: std::time::Duration) {
+        let bucket = TIMING_BUCKET_UPPER_BOUNDS.iter()
+            .position(|upper_bound| duration <= *upper_bound)
+            .unwrap_or(TIMING_BUCKET_UPPER_BOUNDS.len());
+        self.counts[bucket] = self.counts[bucket].saturating_add(1);
+    }
+}
+
+#[cfg(feature = "timing")]
+#[derive(Clone, Default)]
+pub struct ValidationTimings {
+    histograms: std::sync::Arc<std::sync::Mutex<HashMap<String, DurationHistogram>>>
+}
+
+#[cfg(feature = "timing")]
+impl ValidationTimings {
+    pub fn new() -> Self {
+        ValidationTimings::default()
+    }
+
+    pub fn report(&self) -> HashMap<String, DurationHistogram> {
+        self.histograms.lock()
+            .map(|histograms| histograms.clone())
+            .unwrap_or_default()
+    }
+
+    fn record(&self, name: &str, duration: std::time::Duration) {
+        if let Ok(mut histograms) = self.histograms.lock() {
+            histograms.entry(name.to_string()).or_default().record(duration);
+        }
+    }
+}
+
+#[cfg(feature = "timing")]
+pub struct TimedValidator<V: AlicaMessageJsonValidator> {
+    name: String,
+    validator: V,
+    timings: ValidationTimings
+}
+
+#[cfg(feature = "timing")]
+impl<V: AlicaMessageJsonValidator> TimedValidator<V> {
+    pub fn new(name: &str, validator: V, timings: &ValidationTimings) -> Self {
+        TimedValidator {
+            name: name.to_string(),
+            validator,
+            timings: timings.clone()
+        }
+    }
+}
+
+#[cfg(feature = "timing")]
+impl<V: AlicaMessageJsonValidator> AlicaMessageJsonValidator for TimedValidator<V> {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        let start = std::time::Instant::now();
+        let validation_result = self.validator.validate(message);
+        self.timings.record(&self.name, start.elapsed());
+        validation_result
+    }
+}
+
+#[cfg(feature = "json-schema")]
+pub struct JsonSchemaValidator {
+    validator: jsonschema::Validator
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchemaValidator {
+    pub fn from_schema(schema: &[u8]) -> Result<Self, AlicaMessageValidationError> {
+        let schema: serde_json::Value = serde_json::from_slice(schema)
+            .map_err(|_| InvalidFormat(ErrorReason::Malformed, error_text!("Schema is no JSON structure")))?;
+        let validator = jsonschema::validator_for(&schema)
+            .map_err(|error| InvalidFormat(ErrorReason::Malformed, error_text!("Schema is invalid: {}", error)))?;
+
+        Ok(JsonSchemaValidator {
+            validator
+        })
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl AlicaMessageJsonValidator for JsonSchemaValidator {
+    fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+        self.validate_object(&helper::parse_object(message)?)
+    }
+
+    fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+        let message: serde_json::Value = serde_json::from_str(&message.dump())
+            .map_err(|_| InvalidFormat(ErrorReason::Malformed, error_text!("Message is no JSON structure")))?;
+        self.validator.validate(&message)
+            .map_err(|error| InvalidFormat(ErrorReason::DisallowedValue,
+                                           error_text!("{}", crate::helper::truncate_for_error(&error.to_string()))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    mod validation {
+        use crate::messages::json::{validation, CapnZeroIdType, CapnZeroIdValidator};
+        use crate::messages::{AlicaMessageValidationError, MockAlicaMessageJsonValidator};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
+
+        fn as_object(value: json::JsonValue) -> json::object::Object {
+            match value {
+                json::JsonValue::Object(object) => object,
+                _ => panic!("Expected an object")
+            }
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_null_field_is_reported_as_null() {
+            let container = as_object(json::object!{
+                masterPlan: null
+            });
+
+            let validation_result = validation::validate_string_field(&container, "masterPlan");
+
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, message)) => assert_eq!(message, "masterPlan is null"),
+                _ => panic!("Expected an invalid format error")
+            }
+        }
+
+        #[test]
+        fn a_delta_encoded_list_with_non_negative_values_is_valid() {
+            let container = as_object(json::object!{
+                stateIds: [3, 2, 0, 7]
+            });
+
+            let validation_result = validation::validate_delta_encoded_integer_list_field(&container, "stateIds", true);
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_delta_encoded_list_decoding_to_a_negative_value_is_invalid() {
+            let container = as_object(json::object!{
+                stateIds: [3, -2, -2]
+            });
+
+            let validation_result = validation::validate_delta_encoded_integer_list_field(&container, "stateIds", false);
+
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, message)) =>
+                    assert_eq!(message, "stateIds decodes to the negative value -1"),
+                _ => panic!("Expected an invalid format error")
+            }
+        }
+
+        #[test]
+        fn a_delta_encoded_list_with_decreasing_values_is_only_valid_if_allowed() {
+            let container = as_object(json::object!{
+                stateIds: [3, -2]
+            });
+
+            assert!(validation::validate_delta_encoded_integer_list_field(&container, "stateIds", false).is_ok());
+            assert!(validation::validate_delta_encoded_integer_list_field(&container, "stateIds", true).is_err());
+        }
+
+        #[test]
+        fn a_sorted_list_is_valid() {
+            let container = as_object(json::object!{
+                stateIds: [1, 2, 2, 5]
+            });
+
+            assert!(validation::validate_sorted_integer_list_field(&container, "stateIds", true).is_ok());
+            assert!(validation::validate_sorted_integer_list_field(&container, "stateIds", false).is_err());
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn an_unsorted_list_is_invalid() {
+            let container = as_object(json::object!{
+                stateIds: [1, 5, 2]
+            });
+
+            let validation_result = validation::validate_sorted_integer_list_field(&container, "stateIds", true);
+
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, message)) =>
+                    assert_eq!(message, "stateIds is not sorted in ascending order"),
+                _ => panic!("Expected an invalid format error")
+            }
+        }
+
+        #[test]
+        fn empty_and_single_element_lists_are_sorted() {
+            let container = as_object(json::object!{
+                empty: [],
+                single: [4]
+            });
+
+            assert!(validation::validate_sorted_integer_list_field(&container, "empty", true).is_ok());
+            assert!(validation::validate_sorted_integer_list_field(&container, "single", false).is_ok());
+        }
+
+        #[test]
+        fn an_integer_in_u32_range_is_valid() {
+            let container = as_object(json::object!{
+                roleId: 4294967295u64
+            });
+
+            assert!(validation::validate_u32_field(&container, "roleId").is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn an_integer_outside_of_u32_range_is_invalid() {
+            let container = as_object(json::object!{
+                above: 4294967296u64,
+                below: -1
+            });
+
+            assert_eq!(validation::validate_u32_field(&container, "above"),
+                       Err(AlicaMessageValidationError::InvalidFormat(ErrorReason::OutOfRange, "above out of u32 range".to_string())));
+            assert!(validation::validate_u32_field(&container, "below").is_err());
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn an_integer_outside_of_i32_range_is_invalid() {
+            let container = as_object(json::object!{
+                inside: -2147483648i64,
+                outside: 2147483648i64
+            });
+
+            assert!(validation::validate_i32_field(&container, "inside").is_ok());
+            assert_eq!(validation::validate_i32_field(&container, "outside"),
+                       Err(AlicaMessageValidationError::InvalidFormat(ErrorReason::OutOfRange, "outside out of i32 range".to_string())));
+        }
+
+        #[test]
+        fn an_iso8601_date_time_is_valid() {
+            let container = as_object(json::object!{
+                createdAt: "2023-11-14T12:00:00Z"
+            });
+
+            assert!(validation::validate_iso8601_field(&container, "createdAt").is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn an_invalid_date_time_string_is_invalid() {
+            let container = as_object(json::object!{
+                createdAt: "2023-13-45 noon"
+            });
+
+            assert_eq!(validation::validate_iso8601_field(&container, "createdAt"),
+                       Err(AlicaMessageValidationError::InvalidFormat(ErrorReason::Malformed, "createdAt is no ISO-8601 date time".to_string())))
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_non_string_date_time_is_invalid() {
+            let container = as_object(json::object!{
+                createdAt: 1700000000
+            });
+
+            assert_eq!(validation::validate_iso8601_field(&container, "createdAt"),
+                       Err(AlicaMessageValidationError::InvalidFormat(ErrorReason::WrongType, "createdAt is no string".to_string())))
+        }
+
+        #[test]
+        fn a_map_of_strings_is_valid() {
+            let container = as_object(json::object!{
+                metadata: {
+                    robot: "leonardo",
+                    location: "lab"
+                }
+            });
+
+            assert!(validation::validate_string_map_field(&container, "metadata").is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_map_with_a_non_string_value_is_invalid() {
+            let container = as_object(json::object!{
+                metadata: {
+                    robot: "leonardo",
+                    battery: 42
+                }
+            });
+
+            let validation_result = validation::validate_string_map_field(&container, "metadata");
+
+            assert_eq!(validation_result,
+                       Err(AlicaMessageValidationError::InvalidFormat(
+                           ErrorReason::WrongType, "metadata contains a non string value for key battery".to_string())))
+        }
+
+        #[test]
+        fn a_missing_map_is_invalid() {
+            let container = json::object::Object::new();
+
+            let validation_result = validation::validate_string_map_field(&container, "metadata");
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("metadata".to_string())))
+        }
+
+        #[test]
+        fn a_printable_string_is_valid() {
+            let container = as_object(json::object!{
+                masterPlan: "master plan"
+            });
+
+            let validation_result = validation::validate_printable_string_field(&container, "masterPlan");
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_string_with_an_embedded_control_character_is_invalid() {
+            let container = as_object(json::object!{
+                masterPlan: "master\u{0000}plan"
+            });
+
+            let validation_result = validation::validate_printable_string_field(&container, "masterPlan");
+
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, message)) =>
+                    assert_eq!(message, "masterPlan contains control characters"),
+                _ => panic!("Expected an invalid format error")
+            }
+        }
+
+        #[test]
+        fn an_absent_field_is_reported_as_missing() {
+            let container = json::object::Object::new();
+
+            let validation_result = validation::validate_string_field(&container, "masterPlan");
+
+            match validation_result {
+                Err(AlicaMessageValidationError::MissingField(field)) => assert_eq!(field, "masterPlan"),
+                _ => panic!("Expected a missing field error")
+            }
+        }
+
+        #[test]
+        fn a_capnzero_id_can_be_extracted() {
+            let container = as_object(json::object!{
+                senderId: {
+                    type: 1,
+                    value: "42"
+                }
+            });
+
+            let id = validation::extract_capnzero_id(&container, "senderId").expect("Could not extract capnzero id");
+
+            assert_eq!(id.id_type, CapnZeroIdType::Integer);
+            assert_eq!(id.value, "42");
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_malformed_capnzero_id_cannot_be_extracted() {
+            let container = as_object(json::object!{
+                senderId: {
+                    type: 7,
+                    value: "42"
+                }
+            });
+
+            let result = validation::extract_capnzero_id(&container, "senderId");
+
+            assert_eq!(result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::DisallowedValue, "type 7 is no known capnzero id type".to_string())))
+        }
+
+        #[test]
+        fn exactly_one_of_several_fields_is_valid() {
+            let container = as_object(json::object!{
+                planId: 1
+            });
+
+            let validation_result = validation::validate_exactly_one_of(&container, &["planId", "planName"]);
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn none_of_several_fields_is_invalid() {
+            let container = json::object::Object::new();
+
+            let validation_result = validation::validate_exactly_one_of(&container, &["planId", "planName"]);
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::Conflict, "Exactly one of planId, planName is required".to_string())))
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn more_than_one_of_several_fields_is_invalid() {
+            let container = as_object(json::object!{
+                planId: 1,
+                planName: "plan"
+            });
+
+            let validation_result = validation::validate_exactly_one_of(&container, &["planId", "planName"]);
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::Conflict, "Only one of planId, planName is allowed".to_string())))
+        }
+
+        #[test]
+        fn a_finite_float_is_valid() {
+            let container = as_object(json::object!{
+                utility: 0.75
+            });
+
+            let validation_result = validation::validate_float_field(&container, "utility");
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn an_overflowing_float_is_invalid() {
+            for utility in &["1e400", "-1e400"] {
+                let container = as_object(json::parse(&format!("{{\"utility\":{}}}", utility)).unwrap());
+
+                let validation_result = validation::validate_float_field(&container, "utility");
+
+                assert!(validation_result.is_err(), "{} was accepted", utility)
+            }
+        }
+
+        #[test]
+        fn a_not_a_number_float_is_invalid() {
+            let container = as_object(json::object!{
+                utility: json::number::NAN
+            });
+
+            let validation_result = validation::validate_float_field(&container, "utility");
+
+            assert!(validation_result.is_err())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn an_infinite_float_is_reported_as_not_finite() {
+            let container = as_object(json::object!{
+                utility: f64::INFINITY
+            });
+
+            let validation_result = validation::validate_float_field(&container, "utility");
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::OutOfRange, "utility is not finite".to_string())))
+        }
+
+        #[test]
+        fn an_integer_with_an_allowed_value_is_valid() {
+            let container = as_object(json::object!{
+                planType: 2
+            });
+
+            let validation_result = validation::validate_integer_enum_field(&container, "planType", &[1, 2, 3]);
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn an_integer_with_a_disallowed_value_is_invalid() {
+            let container = as_object(json::object!{
+                planType: 4
+            });
+
+            let validation_result = validation::validate_integer_enum_field(&container, "planType", &[1, 2, 3]);
+
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, message)) =>
+                    assert_eq!(message, "planType has disallowed value 4"),
+                _ => panic!("Expected an invalid format error")
+            }
+        }
+
+        #[test]
+        fn a_non_integer_enum_value_is_invalid() {
+            let container = as_object(json::object!{
+                planType: "2"
+            });
+
+            let validation_result = validation::validate_integer_enum_field(&container, "planType", &[1, 2, 3]);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn a_list_at_the_element_cap_is_valid() {
+            let container = as_object(json::object!{
+                stateIds: [1, 2, 3],
+                robots: [{ type: 0, value: "a" }, { type: 0, value: "b" }]
+            });
+
+            assert!(validation::validate_integer_list_field_bounded(&container, "stateIds", 3).is_ok());
+            assert!(validation::validate_list_field_with_complex_components_bounded(&container, "robots",
+                                                                                   &CapnZeroIdValidator::new(), 2).is_ok());
+        }
+
+        #[test]
+        fn complex_list_components_are_validated_without_reparsing() {
+            let container = as_object(json::object!{
+                robots: [{ type: 0, value: "a" }, { type: 0, value: "b" }]
+            });
+            let mut validator = MockAlicaMessageJsonValidator::new();
+            validator.expect_validate().never();
+            validator.expect_validate_object().times(2).returning(|_| Ok(()));
+
+            let validation_result = validation::validate_list_field_with_complex_components(&container, "robots", &validator);
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_list_over_the_element_cap_is_invalid() {
+            let container = as_object(json::object!{
+                stateIds: [1, 2, 3, 4],
+                robots: [{ type: 0, value: "a" }, { type: 0, value: "b" }, { type: 0, value: "c" }]
+            });
+
+            let integer_list_result = validation::validate_integer_list_field_bounded(&container, "stateIds", 3);
+            let complex_list_result = validation::validate_list_field_with_complex_components_bounded(
+                &container, "robots", &CapnZeroIdValidator::new(), 2);
+
+            assert_eq!(integer_list_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::OutOfRange, "stateIds has too many elements".to_string())));
+            assert_eq!(complex_list_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::OutOfRange, "robots has too many elements".to_string())));
+        }
+
+        #[test]
+        fn derived_list_checks_are_capped_at_the_default_element_count() {
+            let mut container = json::object::Object::new();
+            container.insert("stateIds", json::JsonValue::Array(vec![1.into(); validation::DEFAULT_MAX_LIST_ELEMENTS + 1]));
+            container.insert("succeededEps", json::array![1]);
+            let mut metadata = json::object::Object::new();
+            (0..=validation::DEFAULT_MAX_LIST_ELEMENTS).for_each(|key| metadata.insert(&key.to_string(), "value".into()));
+            container.insert("metadata", json::JsonValue::Object(metadata));
+
+            assert!(validation::validate_sorted_integer_list_field(&container, "stateIds", true).is_err());
+            assert!(validation::validate_delta_encoded_integer_list_field(&container, "stateIds", true).is_err());
+            assert!(validation::validate_integer_list_field_is_subset_of(&container, "succeededEps", "stateIds").is_err());
+            assert!(validation::validate_string_map_field(&container, "metadata").is_err());
+        }
+
+        #[test]
+        fn a_numeric_integer_is_valid_in_both_modes() {
+            let container = as_object(json::object!{
+                roleId: 5
+            });
+
+            assert!(validation::validate_integer_field(&container, "roleId").is_ok());
+            assert_eq!(validation::extract_integer(&container, "roleId", true), Ok(5));
+        }
+
+        #[test]
+        fn a_stringified_integer_is_only_valid_in_lenient_mode() {
+            let container = as_object(json::object!{
+                roleId: "5"
+            });
+
+            assert!(validation::validate_integer_field(&container, "roleId").is_err());
+            assert!(validation::validate_lenient_integer_field(&container, "roleId").is_ok());
+            assert_eq!(validation::extract_integer(&container, "roleId", true), Ok(5));
+        }
+
+        #[test]
+        fn a_non_numeric_string_is_invalid_in_lenient_mode() {
+            let container = as_object(json::object!{
+                roleId: "five"
+            });
+
+            let validation_result = validation::validate_lenient_integer_field(&container, "roleId");
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn a_capnzero_id_list_can_be_extracted() {
+            let container = as_object(json::object!{
+                agentIdsWithMe: [{ type: 0, value: "first" }, { type: 1, value: "second" }]
+            });
+
+            let ids = validation::extract_capnzero_id_list(&container, "agentIdsWithMe").expect("Could not extract ids");
+
+            let values: Vec<&str> = ids.iter().map(|id| id.value.as_str()).collect();
+            assert_eq!(values, vec!["first", "second"]);
+            assert_eq!(ids[1].id_type, CapnZeroIdType::Integer);
+        }
+
+        #[test]
+        fn an_invalid_entry_aborts_capnzero_id_list_extraction() {
+            let container = as_object(json::object!{
+                agentIdsWithMe: [{ type: 0, value: "first" }, { type: 0 }]
+            });
+
+            let result = validation::extract_capnzero_id_list(&container, "agentIdsWithMe");
+
+            assert!(result.is_err())
+        }
+    }
+
+    mod alica_engine_info {
+        use crate::messages::json::AlicaEngineInfoValidator;
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
+
+        #[test]
+        fn all_errors_are_reported_in_the_declared_field_order() {
+            let engine_info = json::object!{
+                currentTask: "current task",
+                currentPlan: "current plan",
+                agentIdsWithMe: []
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate_all(engine_info.as_bytes());
+
+            assert_eq!(validation_result, Err(vec![
+                AlicaMessageValidationError::MissingField("senderId".to_string()),
+                AlicaMessageValidationError::MissingField("masterPlan".to_string()),
+                AlicaMessageValidationError::MissingField("currentState".to_string()),
+                AlicaMessageValidationError::MissingField("currentRole".to_string()),
+            ]))
+        }
+
+        #[test]
+        fn the_first_error_in_declared_field_order_is_reported_by_validate() {
+            let engine_info = json::object!{
+                currentTask: "current task",
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("masterPlan".to_string())))
+        }
+
+        #[test]
+        fn it_considers_a_complete_alica_engine_info_valid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agentIdsWithMe: [
+                    {
+                        type: 1,
+                        value: "other agent"
+                    },
+                    {
+                        type: 1,
+                        value: "other other agent"
+                    },
+                ]
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_byte_order_mark_prefixed_alica_engine_info_valid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agentIdsWithMe: []
+            }.dump();
+            let message = ["\u{feff}", engine_info.as_str()].concat();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_an_inner_byte_order_mark_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agentIdsWithMe: []
+            }.dump();
+            let message = [" \u{feff}", engine_info.as_str()].concat();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_sender_id_invalid() {
+            let engine_info = json::object!{}.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_master_plan_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_current_plan_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan"
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_current_state_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan"
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_current_role_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state"
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_current_task_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role"
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_missing_agent_ids_with_me_invalid() {
+            let engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task"
+            }.dump();
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        fn engine_info_with_agents(agent_ids: &[&str]) -> String {
+            let mut engine_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agentIdsWithMe: []
+            };
+            for agent_id in agent_ids {
+                engine_info["agentIdsWithMe"].push(json::object!{
+                    type: 0,
+                    value: *agent_id
+                }).expect("agentIdsWithMe is an array");
+            }
+            engine_info.dump()
+        }
+
+        #[test]
+        fn it_considers_agent_ids_of_consistent_length_valid() {
+            let engine_info = engine_info_with_agents(&["AAAAAAAAAAAAAAAAAAAAAA==", "/////////////////////w=="]);
+
+            let validation_result = AlicaEngineInfoValidator::new().with_id_length_consistency_check()
+                .validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_agent_ids_of_mixed_length_invalid() {
+            let engine_info = engine_info_with_agents(&["AAAAAAAAAAAAAAAAAAAAAA==", "AAAAAA=="]);
+
+            let validation_result = AlicaEngineInfoValidator::new().with_id_length_consistency_check()
+                .validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_ignores_agent_id_lengths_by_default() {
+            let engine_info = engine_info_with_agents(&["AAAAAAAAAAAAAAAAAAAAAA==", "AAAAAA=="]);
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_unique_agent_ids_valid() {
+            let engine_info = engine_info_with_agents(&["a", "b"]);
+
+            let validation_result = AlicaEngineInfoValidator::new().with_id_uniqueness_check()
+                .validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn it_considers_duplicated_agent_ids_invalid_when_checking_uniqueness() {
+            let engine_info = engine_info_with_agents(&["a", "b", "a"]);
+
+            let validation_result = AlicaEngineInfoValidator::new().with_id_uniqueness_check()
+                .validate(engine_info.as_bytes());
+
+            assert_eq!(validation_result,
+                       Err(AlicaMessageValidationError::InvalidFormat(
+                           ErrorReason::DisallowedValue, "agentIdsWithMe contains a more than once".to_string())))
+        }
+
+        #[test]
+        fn it_ignores_duplicated_agent_ids_by_default() {
+            let engine_info = engine_info_with_agents(&["a", "a"]);
+
+            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_non_base64_agent_ids_invalid_when_checking_lengths() {
+            let engine_info = engine_info_with_agents(&["not base64!"]);
+
+            let validation_result = AlicaEngineInfoValidator::new().with_id_length_consistency_check()
+                .validate(engine_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod allocation_authority_info {
+        use crate::messages::json::AllocationAuthorityInfoValidator;
+        use crate::messages::AlicaMessageJsonValidator;
+
+        #[test]
+        fn it_considers_a_complete_allocation_authority_info_valid() {
+            let allocation_authority_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                planId: 1,
+                parentState: 2,
+                planType: 3,
+                authority: {
+                    type: 1,
+                    value: "authority id"
+                },
+                entrypointRobots: [
+
+                ]
+            }.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_allocation_authority_info_without_a_sender_id_invalid() {
+            let allocation_authority_info = json::object!{}.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_allocation_authority_without_a_plan_id_invalid() {
+            let allocation_authority_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_allocation_authority_info_without_a_parent_state_invalid() {
+            let allocation_authority_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                planId: 1
+            }.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_allocation_authority_info_without_a_plan_type_invalid() {
+            let allocation_authority_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                planId: 1,
+                parentState: 2
+            }.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_allocation_authority_info_without_an_authority_invalid() {
+            let allocation_authority_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                planId: 1,
+                parentState: 2,
+                planType: 3
+            }.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_allocation_authority_info_without_a_list_of_entrypoint_robots_invalid() {
+            let allocation_authority_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                planId: 1,
+                parentState: 2,
+                planType: 3,
+                authority: {
+                    type: 1,
+                    value: "authority id"
+                }
+            }.dump();
+
+            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod entry_point_robot {
+        use crate::messages::json::EntryPointRobotValidator;
+        use crate::messages::AlicaMessageJsonValidator;
+
+        #[test]
+        fn it_considers_a_complete_entry_point_robot_valid() {
+            let entry_point_robot = json::object!{
+                entrypoint: 0,
+                robots: [
+                    {
+                        type: 1,
+                        value: "id1"
+                    },
+                    {
+                        type: 1,
+                        value: "id2"
+                    }
+                ]
+            }.dump();
+
+            let validation_result = EntryPointRobotValidator::new().validate(entry_point_robot.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = EntryPointRobotValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = EntryPointRobotValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_entry_point_robot_without_entrypoint_invalid() {
+            let entry_point_robot = json::object!{}.dump();
+
+            let validation_result = EntryPointRobotValidator::new().validate(entry_point_robot.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_an_entry_point_robot_without_robots_invalid() {
+            let entry_point_robot = json::object!{
+                entrypoint: 0
+            }.dump();
+
+            let validation_result = EntryPointRobotValidator::new().validate(entry_point_robot.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod plan_tree_info {
+        use crate::messages::json::PlanTreeInfoValidator;
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
+
+        #[test]
+        fn it_considers_a_complete_plan_tree_info_valid() {
+            let plan_tree_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                stateIds: [1, 2, 3],
+                succeededEps: [4, 5, 6]
+            }.dump();
+
+            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = PlanTreeInfoValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = PlanTreeInfoValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_plan_tree_info_without_a_sender_id_invalid() {
+            let plan_tree_info = json::object!{}.dump();
+
+            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_plan_tree_info_without_state_ids_invalid() {
+            let plan_tree_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_plan_tree_info_without_succeeded_eps_invalid() {
+            let plan_tree_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                stateIds: [1, 2, 3]
+            }.dump();
+
+            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_inconsistent_succeeded_eps_valid_by_default() {
+            let plan_tree_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                stateIds: [1, 2, 3],
+                succeededEps: [4]
+            }.dump();
+
+            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_succeeded_eps_contained_in_state_ids_valid_with_consistency_check() {
+            let plan_tree_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                stateIds: [1, 2, 3],
+                succeededEps: [1, 3]
+            }.dump();
+
+            let validation_result = PlanTreeInfoValidator::new()
+                .with_succeeded_eps_consistency_check()
+                .validate(plan_tree_info.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_succeeded_eps_not_contained_in_state_ids_invalid_with_consistency_check() {
+            let plan_tree_info = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                stateIds: [1, 2, 3],
+                succeededEps: [1, 4]
+            }.dump();
+
+            let validation_result = PlanTreeInfoValidator::new()
+                .with_succeeded_eps_consistency_check()
+                .validate(plan_tree_info.as_bytes());
+
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, _)) => (),
+                _ => panic!("Expected an invalid format error")
+            }
+        }
+    }
+
+    mod role_switch {
+        use crate::messages::json::{CapnZeroId, CapnZeroIdType, RoleSwitch, RoleSwitchValidator};
+        use crate::messages::AlicaMessageJsonValidator;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::messages::AlicaMessageValidationError;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn it_reports_an_empty_role_switch_as_empty_object() {
+            let validation_result = RoleSwitchValidator::new().validate(json::object!{}.dump().as_bytes());
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::Malformed, "message object is empty".to_string())))
+        }
+
+        #[test]
+        fn a_role_switch_can_be_extracted_into_a_typed_struct() {
+            let role_switch = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 4
+            }.dump();
+
+            let result = RoleSwitch::from_json(role_switch.as_bytes()).expect("Could not extract role switch");
+
+            assert_eq!(result, RoleSwitch {
+                sender_id: CapnZeroId { id_type: CapnZeroIdType::Uuid, value: "id".to_string() },
+                role_id: 4
+            })
+        }
+
+        #[test]
+        fn an_invalid_role_switch_cannot_be_extracted() {
+            let role_switch = json::object!{
+                roleId: 4
+            }.dump();
+
+            let result = RoleSwitch::from_json(role_switch.as_bytes());
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_complete_role_switch_valid() {
+            let role_switch = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 1
+            }.dump();
+
+            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = RoleSwitchValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = RoleSwitchValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_role_switch_without_sender_id_invalid() {
+            let role_switch = json::object!{}.dump();
+
+            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_role_switch_with_a_role_id_outside_of_u32_range_invalid() {
+            let role_switch = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 4294967296u64
+            }.dump();
+
+            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_role_switch_wihtout_a_role_id_invalid() {
+            let role_switch = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod solver_result {
+        use crate::messages::json::SolverResultValidator;
+        use crate::messages::AlicaMessageJsonValidator;
+
+        #[test]
+        fn it_considers_a_complete_solver_result_valid() {
+            let solver_result = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                vars: [
+                    {
+                        id: 0,
+                        value: [1, 2, 3]
+                    },
+                    {
+                        id: 1,
+                        value: [4, 5, 6]
+                    }
+                ]
+            }.dump();
+
+            let validation_result = SolverResultValidator::new().validate(solver_result.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = SolverResultValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = SolverResultValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_solver_result_without_a_sender_id_invalid() {
+            let role_switch = json::object!{}.dump();
+
+            let validation_result = SolverResultValidator::new().validate(role_switch.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_solver_result_without_variables_invalid() {
+            let role_switch = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = SolverResultValidator::new().validate(role_switch.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod solver_var {
+        use crate::messages::json::SolverVarValidator;
+        use crate::messages::AlicaMessageJsonValidator;
+
+        #[test]
+        fn it_considers_a_complete_solver_var_valid() {
+            let solver_var = json::object!{
+                id: 0,
+                value: [0, 1, 2]
+            }.dump();
+
+            let validation_result = SolverVarValidator::new().validate(solver_var.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = SolverVarValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = SolverVarValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_solver_var_wihtout_an_id_invalid() {
+            let solver_var = json::object!{}.dump();
+
+            let validation_result = SolverVarValidator::new().validate(solver_var.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_solver_var_without_value_invalid() {
+            let solver_var = json::object!{
+                id: 0
+            }.dump();
+
+            let validation_result = SolverVarValidator::new().validate(solver_var.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod sync_ready {
+        use crate::messages::json::SyncReadyValidator;
+        use crate::messages::AlicaMessageJsonValidator;
+
+        #[test]
+        fn it_considers_a_complete_sync_ready_valid() {
+            let sync_ready = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                synchronisationId: 1
+            }.dump();
+
+            let validation_result = SyncReadyValidator::new().validate(sync_ready.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = SyncReadyValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = SyncReadyValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_sync_ready_without_a_sender_id_invalid() {
+            let sync_ready = json::object!{}.dump();
+
+            let validation_result = SyncReadyValidator::new().validate(sync_ready.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_sync_ready_without_a_synchronisation_id_invalid() {
+            let sync_ready = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+
+            let validation_result = SyncReadyValidator::new().validate(sync_ready.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod sync_talk {
+        use crate::messages::json::SyncTalkValidator;
+        use crate::messages::AlicaMessageJsonValidator;
+
+        #[test]
+        fn it_considers_a_complete_sync_talk_valid() {
+            let sync_talk = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                syncData: [
+                    {
+                        robotId: {
+                            type: 1,
+                            value: "robot1"
+                        },
+                        transitionId: 1,
+                        transitionHolds: false,
+                        ack: true
+                    },
+                    {
+                        robotId: {
+                            type: 1,
+                            value: "robot2"
+                        },
+                        transitionId: 2,
+                        transitionHolds: true,
+                        ack: false
+                    },
+                ]
+            }.dump();
+
+            let validation_result = SyncTalkValidator::new().validate(sync_talk.as_bytes());
+
+            assert!(validation_result.is_ok())
         }
 
         #[test]
-        fn it_considers_an_alica_engine_info_with_missing_current_plan_invalid() {
-            let engine_info = json::object!{
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = SyncTalkValidator::new().validate(&message);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = SyncTalkValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_sync_talk_without_a_sender_id_invalid() {
+            let sync_talk = json::object!{}.dump();
+
+            let validation_result = SyncTalkValidator::new().validate(sync_talk.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_sync_talk_without_sync_data_invalid() {
+            let sync_talk = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
-                },
-                masterPlan: "master plan"
+                }
             }.dump();
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+            let validation_result = SyncTalkValidator::new().validate(sync_talk.as_bytes());
 
             assert!(validation_result.is_err())
         }
+    }
+
+    mod sync_data {
+        use crate::messages::json::{CapnZeroId, CapnZeroIdType, SyncData, SyncDataValidator};
+        use crate::messages::AlicaMessageJsonValidator;
 
         #[test]
-        fn it_considers_an_alica_engine_info_with_missing_current_state_invalid() {
-            let engine_info = json::object!{
-                senderId: {
+        fn sync_data_can_be_extracted_into_a_typed_struct() {
+            let sync_data = json::object!{
+                robotId: {
+                    type: 1,
+                    value: "7"
+                },
+                transitionId: 12,
+                transitionHolds: true,
+                ack: false
+            }.dump();
+
+            let result = SyncData::from_json(sync_data.as_bytes()).expect("Could not extract sync data");
+
+            assert_eq!(result, SyncData {
+                robot_id: CapnZeroId { id_type: CapnZeroIdType::Integer, value: "7".to_string() },
+                transition_id: 12,
+                transition_holds: true,
+                ack: false
+            })
+        }
+
+        #[test]
+        fn it_considers_a_complete_sync_data_valid() {
+            let sync_talk = json::object!{
+                robotId: {
                     type: 0,
                     value: "id"
                 },
-                masterPlan: "master plan",
-                currentPlan: "current plan"
+                transitionId: 1,
+                transitionHolds: true,
+                ack: true
             }.dump();
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0x0];
+
+            let validation_result = SyncDataValidator::new().validate(&message);
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_alica_engine_info_with_missing_current_role_invalid() {
-            let engine_info = json::object!{
-                senderId: {
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = SyncDataValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_sync_data_without_robot_id_invalid() {
+            let sync_talk = json::object!{}.dump();
+
+            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_sync_data_without_transition_id_invalid() {
+            let sync_talk = json::object!{
+                robotId: {
                     type: 0,
                     value: "id"
-                },
-                masterPlan: "master plan",
-                currentPlan: "current plan",
-                currentState: "current state"
+                }
             }.dump();
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_alica_engine_info_with_missing_current_task_invalid() {
-            let engine_info = json::object!{
-                senderId: {
+        fn it_considers_a_sync_data_without_transition_holds_status_invalid() {
+            let sync_talk = json::object!{
+                robotId: {
                     type: 0,
                     value: "id"
                 },
-                masterPlan: "master plan",
-                currentPlan: "current plan",
-                currentState: "current state",
-                currentRole: "current role"
+                transitionId: 1
             }.dump();
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_alica_engine_info_with_missing_agent_ids_with_me_invalid() {
-            let engine_info = json::object!{
-                senderId: {
+        fn it_considers_a_sync_data_without_ack_status_invalid() {
+            let sync_talk = json::object!{
+                robotId: {
                     type: 0,
                     value: "id"
                 },
-                masterPlan: "master plan",
-                currentPlan: "current plan",
-                currentState: "current state",
-                currentRole: "current role",
-                currentTask: "current task"
+                transitionId: 1,
+                transitionHolds: true
             }.dump();
 
-            let validation_result = AlicaEngineInfoValidator::new().validate(engine_info.as_bytes());
+            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
 
             assert!(validation_result.is_err())
         }
     }
 
-    mod allocation_authority_info {
-        use crate::messages::json::AllocationAuthorityInfoValidator;
-        use crate::messages::AlicaMessageJsonValidator;
+    mod pose {
+        use crate::messages::json::PoseValidator;
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
 
         #[test]
-        fn it_considers_a_complete_allocation_authority_info_valid() {
-            let allocation_authority_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                },
-                planId: 1,
-                parentState: 2,
-                planType: 3,
-                authority: {
-                    type: 1,
-                    value: "authority id"
-                },
-                entrypointRobots: [
+        fn it_considers_a_complete_pose_valid() {
+            let pose = json::object!{
+                x: 1.5,
+                y: -2,
+                theta: 0.75,
+                frame: "map"
+            }.dump();
 
-                ]
+            let validation_result = PoseValidator::new().validate(pose.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_pose_without_frame_valid() {
+            let pose = json::object!{
+                x: 1.5,
+                y: -2,
+                theta: 0.75
             }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = PoseValidator::new().validate(pose.as_bytes());
 
             assert!(validation_result.is_ok())
         }
 
         #[test]
         fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+            let message = vec![0xff];
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(&message);
+            let validation_result = PoseValidator::new().validate(&message);
 
             assert!(validation_result.is_err())
         }
@@ -488,125 +3045,203 @@ mod test {
         fn it_considers_a_non_json_message_invalid() {
             let message = "";
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(message.as_bytes());
+            let validation_result = PoseValidator::new().validate(message.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_allocation_authority_info_without_a_sender_id_invalid() {
-            let allocation_authority_info = json::object!{}.dump();
+        fn it_considers_a_pose_without_theta_invalid() {
+            let pose = json::object!{
+                x: 1.5,
+                y: -2
+            }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = PoseValidator::new().validate(pose.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("theta".to_string())))
         }
 
+        #[cfg(not(feature = "compact-errors"))]
         #[test]
-        fn it_considers_an_allocation_authority_without_a_plan_id_invalid() {
-            let allocation_authority_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                }
+        fn it_considers_a_pose_with_a_non_numeric_coordinate_invalid() {
+            let pose = json::object!{
+                x: "1.5",
+                y: -2,
+                theta: 0.75
             }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = PoseValidator::new().validate(pose.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(ErrorReason::WrongType, "x is no number".to_string())))
         }
 
         #[test]
-        fn it_considers_an_allocation_authority_info_without_a_parent_state_invalid() {
-            let allocation_authority_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                },
-                planId: 1
+        fn it_considers_a_pose_with_a_non_string_frame_invalid() {
+            let pose = json::object!{
+                x: 1.5,
+                y: -2,
+                theta: 0.75,
+                frame: 1
             }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = PoseValidator::new().validate(pose.as_bytes());
 
             assert!(validation_result.is_err())
         }
+    }
+
+    mod bid {
+        use crate::messages::json::BidValidator;
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
+
+        fn bid_without(field: &str) -> String {
+            let mut bid = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                taskId: 3,
+                utility: 0.75
+            };
+            bid.remove(field);
+            bid.dump()
+        }
 
         #[test]
-        fn it_considers_an_allocation_authority_info_without_a_plan_type_invalid() {
-            let allocation_authority_info = json::object!{
+        fn it_considers_a_complete_bid_valid() {
+            let bid = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 },
-                planId: 1,
-                parentState: 2
+                taskId: 3,
+                utility: 0.75
             }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = BidValidator::new().validate(bid.as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_a_non_utf8_message_invalid() {
+            let message = vec![0xff];
+
+            let validation_result = BidValidator::new().validate(&message);
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_allocation_authority_info_without_an_authority_invalid() {
-            let allocation_authority_info = json::object!{
+        fn it_considers_a_non_json_message_invalid() {
+            let message = "";
+
+            let validation_result = BidValidator::new().validate(message.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn it_considers_a_bid_without_sender_id_invalid() {
+            let validation_result = BidValidator::new().validate(bid_without("senderId").as_bytes());
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("senderId".to_string())))
+        }
+
+        #[test]
+        fn it_considers_a_bid_without_task_id_invalid() {
+            let validation_result = BidValidator::new().validate(bid_without("taskId").as_bytes());
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("taskId".to_string())))
+        }
+
+        #[test]
+        fn it_considers_a_bid_without_utility_invalid() {
+            let validation_result = BidValidator::new().validate(bid_without("utility").as_bytes());
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("utility".to_string())))
+        }
+
+        #[test]
+        fn it_considers_a_bid_with_a_non_numeric_utility_invalid() {
+            let bid = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 },
-                planId: 1,
-                parentState: 2,
-                planType: 3
+                taskId: 3,
+                utility: "high"
             }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = BidValidator::new().validate(bid.as_bytes());
+
+            assert!(validation_result.is_err())
+        }
+    }
+
+    mod capnzero_id {
+        use crate::messages::json::{helper, CapnZeroIdValidator, CapnZeroIdType, CapnZeroId, CapnZeroIdValueEncoding};
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
+
+        fn encoding_checking_validator() -> CapnZeroIdValidator {
+            CapnZeroIdValidator::new()
+                .with_value_encoding(CapnZeroIdType::Uuid, CapnZeroIdValueEncoding::Base64)
+                .with_value_encoding(CapnZeroIdType::Integer, CapnZeroIdValueEncoding::Integer)
+        }
+
+        #[test]
+        fn it_considers_a_value_matching_the_encoding_of_its_type_valid() {
+            let uuid = json::object!{ type: 0, value: "AAAAAAAAAAAAAAAAAAAAAA==" }.dump();
+            let integer = json::object!{ type: 1, value: "42" }.dump();
+
+            assert!(encoding_checking_validator().validate(uuid.as_bytes()).is_ok());
+            assert!(encoding_checking_validator().validate(integer.as_bytes()).is_ok());
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn it_considers_a_value_not_matching_the_encoding_of_its_type_invalid() {
+            let integer = json::object!{ type: 1, value: "forty two" }.dump();
 
-            assert!(validation_result.is_err())
+            let validation_result = encoding_checking_validator().validate(integer.as_bytes());
+
+            assert_eq!(validation_result,
+                       Err(AlicaMessageValidationError::InvalidFormat(
+                           ErrorReason::DisallowedValue, "value does not match the encoding of type 1".to_string())))
         }
 
         #[test]
-        fn it_considers_an_allocation_authority_info_without_a_list_of_entrypoint_robots_invalid() {
-            let allocation_authority_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                },
-                planId: 1,
-                parentState: 2,
-                planType: 3,
-                authority: {
-                    type: 1,
-                    value: "authority id"
-                }
-            }.dump();
+        fn it_ignores_value_encodings_by_default() {
+            let integer = json::object!{ type: 1, value: "forty two" }.dump();
 
-            let validation_result = AllocationAuthorityInfoValidator::new().validate(allocation_authority_info.as_bytes());
+            let validation_result = CapnZeroIdValidator::new().validate(integer.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert!(validation_result.is_ok())
         }
-    }
 
-    mod entry_point_robot {
-        use crate::messages::json::EntryPointRobotValidator;
-        use crate::messages::AlicaMessageJsonValidator;
+        #[test]
+        fn a_capnzero_id_message_can_be_extracted_into_a_typed_struct() {
+            let capnzero_id = json::object!{
+                type: 2,
+                value: "*"
+            }.dump();
+
+            let result = CapnZeroId::parse(capnzero_id.as_bytes()).expect("Could not extract capnzero id");
+
+            assert_eq!(result, CapnZeroId { id_type: CapnZeroIdType::Wildcard, value: "*".to_string() })
+        }
 
         #[test]
-        fn it_considers_a_complete_entry_point_robot_valid() {
-            let entry_point_robot = json::object!{
-                entrypoint: 0,
-                robots: [
-                    {
-                        type: 1,
-                        value: "id1"
-                    },
-                    {
-                        type: 1,
-                        value: "id2"
-                    }
-                ]
+        fn it_considers_a_complete_capnzero_id_valid() {
+            let capnzero_id = json::object!{
+                type: 0,
+                value: "id"
             }.dump();
 
-            let validation_result = EntryPointRobotValidator::new().validate(entry_point_robot.as_bytes());
+            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
 
             assert!(validation_result.is_ok())
         }
@@ -615,7 +3250,7 @@ mod test {
         fn it_considers_a_non_utf8_message_invalid() {
             let message = vec![0x0];
 
-            let validation_result = EntryPointRobotValidator::new().validate(&message);
+            let validation_result = CapnZeroIdValidator::new().validate(&message);
 
             assert!(validation_result.is_err())
         }
@@ -624,582 +3259,869 @@ mod test {
         fn it_considers_a_non_json_message_invalid() {
             let message = "";
 
-            let validation_result = EntryPointRobotValidator::new().validate(message.as_bytes());
+            let validation_result = CapnZeroIdValidator::new().validate(message.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_entry_point_robot_without_entrypoint_invalid() {
-            let entry_point_robot = json::object!{}.dump();
+        fn it_considers_an_id_without_a_type_invalid() {
+            let capnzero_id = json::object!{}.dump();
 
-            let validation_result = EntryPointRobotValidator::new().validate(entry_point_robot.as_bytes());
+            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_entry_point_robot_without_robots_invalid() {
-            let entry_point_robot = json::object!{
-                entrypoint: 0
+        fn it_considers_an_id_without_a_value_invalid() {
+            let capnzero_id = json::object!{
+                type: 0
             }.dump();
 
-            let validation_result = EntryPointRobotValidator::new().validate(entry_point_robot.as_bytes());
+            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
 
             assert!(validation_result.is_err())
         }
-    }
-
-    mod plan_tree_info {
-        use crate::messages::json::PlanTreeInfoValidator;
-        use crate::messages::AlicaMessageJsonValidator;
 
+        #[cfg(not(feature = "compact-errors"))]
         #[test]
-        fn it_considers_a_complete_plan_tree_info_valid() {
-            let plan_tree_info = json::object!{
-                senderId: {
-                    type: 0,
-                    value: "id"
-                },
-                stateIds: [1, 2, 3],
-                succeededEps: [4, 5, 6]
+        fn it_considers_an_id_with_an_unknown_type_invalid() {
+            let capnzero_id = json::object!{
+                type: 42,
+                value: "id"
             }.dump();
 
-            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
 
-            assert!(validation_result.is_ok())
+            match validation_result {
+                Err(AlicaMessageValidationError::InvalidFormat(_, message)) =>
+                    assert_eq!(message, "type 42 is no known capnzero id type"),
+                _ => panic!("Expected an invalid format error")
+            }
         }
 
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+        fn known_id_types_map_to_their_variant() {
+            assert_eq!(CapnZeroIdType::from_i64(0), Some(CapnZeroIdType::Uuid));
+            assert_eq!(CapnZeroIdType::from_i64(1), Some(CapnZeroIdType::Integer));
+            assert_eq!(CapnZeroIdType::from_i64(2), Some(CapnZeroIdType::Wildcard));
+        }
 
-            let validation_result = PlanTreeInfoValidator::new().validate(&message);
+        fn as_object(value: json::JsonValue) -> json::object::Object {
+            helper::parse_object(value.dump().as_bytes()).expect("Expected an object")
+        }
 
-            assert!(validation_result.is_err())
+        #[test]
+        fn equal_ids_compare_equal() {
+            let id = CapnZeroId::from_json(&as_object(json::object!{ type: 0, value: "id" }))
+                .expect("Could not read id");
+            let other_id = CapnZeroId::from_json(&as_object(json::object!{ value: "id", type: 0 }))
+                .expect("Could not read id");
+
+            assert_eq!(id, other_id)
         }
 
         #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
+        fn ids_of_different_types_compare_unequal() {
+            let id = CapnZeroId::from_json(&as_object(json::object!{ type: 0, value: "id" }))
+                .expect("Could not read id");
+            let other_id = CapnZeroId::from_json(&as_object(json::object!{ type: 1, value: "id" }))
+                .expect("Could not read id");
 
-            let validation_result = PlanTreeInfoValidator::new().validate(message.as_bytes());
+            assert_ne!(id, other_id)
+        }
 
-            assert!(validation_result.is_err())
+        #[test]
+        fn incomplete_ids_cannot_be_read() {
+            let result = CapnZeroId::from_json(&as_object(json::object!{ type: 0 }));
+
+            assert_eq!(result, Err(AlicaMessageValidationError::MissingField("value".to_string())))
         }
 
         #[test]
-        fn it_considers_a_plan_tree_info_without_a_sender_id_invalid() {
-            let plan_tree_info = json::object!{}.dump();
+        fn id_types_map_back_to_their_integer() {
+            assert_eq!(CapnZeroIdType::Integer.as_i64(), 1)
+        }
 
-            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+        #[test]
+        fn unknown_id_types_map_to_nothing() {
+            assert_eq!(CapnZeroIdType::from_i64(-1), None)
+        }
+    }
+
+    mod aliased {
+        use crate::messages::json::{AlicaEngineInfoValidator, AliasedValidator, FieldAliasMap};
+        use crate::messages::AlicaMessageJsonValidator;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::messages::AlicaMessageValidationError;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
+
+        fn engine_info_with_snake_case_sender_id() -> String {
+            json::object!{
+                sender_id: {
+                    type: 0,
+                    value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agentIdsWithMe: []
+            }.dump()
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_an_aliased_sender_id_valid() {
+            let alias_map = FieldAliasMap::new().with_alias("senderId", "sender_id");
+            let validator = AliasedValidator::new(alias_map, AlicaEngineInfoValidator::new());
+
+            let validation_result = validator.validate(engine_info_with_snake_case_sender_id().as_bytes());
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn it_considers_an_alica_engine_info_with_an_unknown_alias_invalid() {
+            let validator = AliasedValidator::new(FieldAliasMap::new(), AlicaEngineInfoValidator::new());
+
+            let validation_result = validator.validate(engine_info_with_snake_case_sender_id().as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_a_plan_tree_info_without_state_ids_invalid() {
-            let plan_tree_info = json::object!{
+        fn it_does_not_apply_aliases_to_nested_objects() {
+            let alias_map = FieldAliasMap::new().with_alias("agentIdsWithMe", "agent_ids_with_me")
+                .with_alias("value", "id_value");
+            let validator = AliasedValidator::new(alias_map, AlicaEngineInfoValidator::new());
+            let engine_info = json::object!{
                 senderId: {
                     type: 0,
-                    value: "id"
-                }
+                    id_value: "id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agent_ids_with_me: [
+                    {
+                        type: 1,
+                        id_value: "other agent"
+                    }
+                ]
             }.dump();
 
-            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+            let validation_result = validator.validate(engine_info.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
+        #[cfg(not(feature = "compact-errors"))]
         #[test]
-        fn it_considers_a_plan_tree_info_without_succeeded_eps_invalid() {
-            let plan_tree_info = json::object!{
+        fn it_considers_a_message_with_a_field_and_its_alias_invalid() {
+            let alias_map = FieldAliasMap::new().with_alias("senderId", "sender_id");
+            let validator = AliasedValidator::new(alias_map, AlicaEngineInfoValidator::new());
+            let engine_info = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 },
-                stateIds: [1, 2, 3]
+                sender_id: {
+                    type: 0,
+                    value: "other id"
+                },
+                masterPlan: "master plan",
+                currentPlan: "current plan",
+                currentState: "current state",
+                currentRole: "current role",
+                currentTask: "current task",
+                agentIdsWithMe: []
             }.dump();
 
-            let validation_result = PlanTreeInfoValidator::new().validate(plan_tree_info.as_bytes());
+            let validation_result = validator.validate(engine_info.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::Conflict, "senderId is present more than once under its aliases".to_string())))
         }
     }
 
-    mod role_switch {
-        use crate::messages::json::RoleSwitchValidator;
-        use crate::messages::AlicaMessageJsonValidator;
+    mod with_sender_id {
+        use crate::messages::json::{helper, validation, ClosureValidator, WithSenderId};
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError, AlicaMessageValidationResult};
+
+        fn task_validator() -> WithSenderId<impl AlicaMessageJsonValidator> {
+            WithSenderId::new(ClosureValidator::new(|message: &[u8]| {
+                let task = helper::parse_object(message)?;
+                validation::validate_integer_field(&task, "taskId")
+            }))
+        }
 
         #[test]
-        fn it_considers_a_complete_role_switch_valid() {
-            let role_switch = json::object!{
+        fn it_considers_a_message_with_sender_id_and_inner_fields_valid() {
+            let message = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 },
-                roleId: 1
+                taskId: 1
             }.dump();
 
-            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+            let validation_result = task_validator().validate(message.as_bytes());
 
             assert!(validation_result.is_ok())
         }
 
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
-
-            let validation_result = RoleSwitchValidator::new().validate(&message);
-
-            assert!(validation_result.is_err())
-        }
-
-        #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
+        fn it_considers_a_message_without_sender_id_invalid() {
+            let message = json::object!{
+                taskId: 1
+            }.dump();
 
-            let validation_result = RoleSwitchValidator::new().validate(message.as_bytes());
+            let validation_result = task_validator().validate(message.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("senderId".to_string())))
         }
 
         #[test]
-        fn it_considers_a_role_switch_without_sender_id_invalid() {
-            let role_switch = json::object!{}.dump();
+        fn it_considers_a_message_with_an_invalid_sender_id_invalid() {
+            let message = json::object!{
+                senderId: {
+                    type: 0
+                },
+                taskId: 1
+            }.dump();
 
-            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+            let validation_result = task_validator().validate(message.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_a_role_switch_wihtout_a_role_id_invalid() {
-            let role_switch = json::object!{
+        fn it_considers_a_message_without_the_inner_field_invalid() {
+            let message = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 }
             }.dump();
 
-            let validation_result = RoleSwitchValidator::new().validate(role_switch.as_bytes());
+            let validation_result = task_validator().validate(message.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("taskId".to_string())))
         }
-    }
 
-    mod solver_result {
-        use crate::messages::json::SolverResultValidator;
-        use crate::messages::AlicaMessageJsonValidator;
+        struct ParsedTaskValidator {}
+
+        impl AlicaMessageJsonValidator for ParsedTaskValidator {
+            fn validate(&self, _: &[u8]) -> AlicaMessageValidationResult {
+                panic!("The message was parsed again")
+            }
+
+            fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+                validation::validate_integer_field(message, "taskId")
+            }
+        }
 
         #[test]
-        fn it_considers_a_complete_solver_result_valid() {
-            let solver_result = json::object!{
+        fn the_inner_validator_receives_the_parsed_message() {
+            let message = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 },
-                vars: [
-                    {
-                        id: 0,
-                        value: [1, 2, 3]
-                    },
-                    {
-                        id: 1,
-                        value: [4, 5, 6]
-                    }
-                ]
+                taskId: 1
             }.dump();
 
-            let validation_result = SolverResultValidator::new().validate(solver_result.as_bytes());
+            let validation_result = WithSenderId::new(ParsedTaskValidator {}).validate(message.as_bytes());
 
             assert!(validation_result.is_ok())
         }
+    }
 
-        #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
-
-            let validation_result = SolverResultValidator::new().validate(&message);
+    mod all_validators {
+        use crate::messages::json::all_validators;
 
-            assert!(validation_result.is_err())
+        #[test]
+        fn it_lists_every_built_in_validator() {
+            let names: Vec<&str> = all_validators().iter().map(|(name, _)| *name).collect();
+
+            assert_eq!(names, vec![
+                "AlicaEngineInfo",
+                "AllocationAuthorityInfo",
+                "EntryPointRobot",
+                "PlanTreeInfo",
+                "RoleSwitch",
+                "SolverResult",
+                "SolverVar",
+                "SyncReady",
+                "SyncTalk",
+                "SyncData",
+                "CapnZeroId",
+                "Bid",
+                "Pose",
+            ])
         }
 
         #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
-
-            let validation_result = SolverResultValidator::new().validate(message.as_bytes());
-
-            assert!(validation_result.is_err())
+        fn every_listed_validator_rejects_non_json_messages() {
+            for (name, validator) in all_validators() {
+                assert!(validator.validate("".as_bytes()).is_err(), "{} accepted a non JSON message", name)
+            }
         }
+    }
+
+    mod classify {
+        use crate::messages::json::classify;
 
         #[test]
-        fn it_considers_a_solver_result_without_a_sender_id_invalid() {
-            let role_switch = json::object!{}.dump();
+        fn a_sync_data_is_only_accepted_by_the_sync_data_validator() {
+            let sync_data = json::object!{
+                robotId: {
+                    type: 0,
+                    value: "id"
+                },
+                transitionId: 1,
+                transitionHolds: true,
+                ack: false
+            }.dump();
 
-            let validation_result = SolverResultValidator::new().validate(role_switch.as_bytes());
+            let results = classify(sync_data.as_bytes());
 
-            assert!(validation_result.is_err())
+            let accepted: Vec<&str> = results.iter()
+                .filter(|(_, result)| result.is_ok())
+                .map(|(message_type, _)| *message_type)
+                .collect();
+            assert_eq!(results.len(), 13);
+            assert_eq!(accepted, vec!["SyncData"]);
         }
 
         #[test]
-        fn it_considers_a_solver_result_without_variables_invalid() {
-            let role_switch = json::object!{
+        fn a_plan_tree_info_is_only_accepted_by_the_plan_tree_info_validator() {
+            let plan_tree_info = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
-                }
+                },
+                stateIds: [1, 2],
+                succeededEps: []
             }.dump();
 
-            let validation_result = SolverResultValidator::new().validate(role_switch.as_bytes());
+            let results = classify(plan_tree_info.as_bytes());
 
-            assert!(validation_result.is_err())
+            let accepted: Vec<&str> = results.iter()
+                .filter(|(_, result)| result.is_ok())
+                .map(|(message_type, _)| *message_type)
+                .collect();
+            assert_eq!(accepted, vec!["PlanTreeInfo"]);
         }
     }
 
-    mod solver_var {
-        use crate::messages::json::SolverVarValidator;
-        use crate::messages::AlicaMessageJsonValidator;
-
-        #[test]
-        fn it_considers_a_complete_solver_var_valid() {
-            let solver_var = json::object!{
-                id: 0,
-                value: [0, 1, 2]
-            }.dump();
-
-            let validation_result = SolverVarValidator::new().validate(solver_var.as_bytes());
+    mod validate_and_canonicalize {
+        use crate::messages::json::validate_and_canonicalize;
+        use crate::messages::registry::ValidatorRegistry;
 
-            assert!(validation_result.is_ok())
+        #[test]
+        fn equivalent_messages_canonicalize_to_identical_bytes() {
+            let registry = ValidatorRegistry::with_defaults();
+            let sync_data = r#"{"transitionId": 1, "robotId": {"value": "id", "type": 0}, "ack": false, "transitionHolds": true}"#;
+            let reformatted_sync_data = "{\n  \"ack\" : false,\n  \"transitionHolds\" : true,\n  \"robotId\" : {\"type\" : 0, \"value\" : \"id\"},\n  \"transitionId\" : 1\n}";
+
+            let canonical = validate_and_canonicalize("SyncData", sync_data.as_bytes(), &registry)
+                .expect("Could not canonicalize message");
+            let reformatted_canonical = validate_and_canonicalize("SyncData", reformatted_sync_data.as_bytes(), &registry)
+                .expect("Could not canonicalize message");
+
+            assert_eq!(canonical, reformatted_canonical);
+            assert_eq!(canonical, r#"{"ack":false,"robotId":{"type":0,"value":"id"},"transitionHolds":true,"transitionId":1}"#.as_bytes().to_vec());
         }
 
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+        fn messages_with_a_byte_order_mark_are_canonicalized() {
+            let registry = ValidatorRegistry::with_defaults();
+            let sync_data = r#"{"transitionId":1,"robotId":{"type":0,"value":"id"},"ack":false,"transitionHolds":true}"#;
+            let sync_data_with_bom = [&[0xef, 0xbb, 0xbf], sync_data.as_bytes()].concat();
 
-            let validation_result = SolverVarValidator::new().validate(&message);
+            let canonical = validate_and_canonicalize("SyncData", sync_data.as_bytes(), &registry)
+                .expect("Could not canonicalize message");
+            let canonical_with_bom = validate_and_canonicalize("SyncData", &sync_data_with_bom, &registry)
+                .expect("Could not canonicalize message");
 
-            assert!(validation_result.is_err())
+            assert_eq!(canonical, canonical_with_bom)
         }
 
         #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
+        fn invalid_messages_are_not_canonicalized() {
+            let registry = ValidatorRegistry::with_defaults();
 
-            let validation_result = SolverVarValidator::new().validate(message.as_bytes());
+            let result = validate_and_canonicalize("SyncData", "{}".as_bytes(), &registry);
 
-            assert!(validation_result.is_err())
+            assert!(result.is_err())
         }
+    }
 
-        #[test]
-        fn it_considers_a_solver_var_wihtout_an_id_invalid() {
-            let solver_var = json::object!{}.dump();
-
-            let validation_result = SolverVarValidator::new().validate(solver_var.as_bytes());
+    mod unknown_fields {
+        use crate::messages::json::{RoleSwitchValidator, UnknownFieldsValidator};
+        use crate::messages::{AlicaMessageJsonValidator, Warning};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::messages::AlicaMessageValidationError;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
 
-            assert!(validation_result.is_err())
+        fn role_switch_with_extra_field() -> String {
+            json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 1,
+                comment: "extra"
+            }.dump()
         }
 
         #[test]
-        fn it_considers_a_solver_var_without_value_invalid() {
-            let solver_var = json::object!{
-                id: 0
-            }.dump();
+        fn an_extra_field_produces_a_warning_but_validates() {
+            let validator = UnknownFieldsValidator::new(RoleSwitchValidator::new(), &["senderId", "roleId"]);
 
-            let validation_result = SolverVarValidator::new().validate(solver_var.as_bytes());
+            let warnings = validator.validate_with_warnings(role_switch_with_extra_field().as_bytes())
+                .expect("Expected the message to be valid");
 
-            assert!(validation_result.is_err())
+            assert_eq!(warnings, vec![Warning::UnknownField("comment".to_string())])
         }
-    }
-
-    mod sync_ready {
-        use crate::messages::json::SyncReadyValidator;
-        use crate::messages::AlicaMessageJsonValidator;
 
         #[test]
-        fn it_considers_a_complete_sync_ready_valid() {
-            let sync_ready = json::object!{
+        fn a_message_without_extra_fields_produces_no_warnings() {
+            let validator = UnknownFieldsValidator::new(RoleSwitchValidator::new(), &["senderId", "roleId"]);
+            let role_switch = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
                 },
-                synchronisationId: 1
+                roleId: 1
             }.dump();
 
-            let validation_result = SyncReadyValidator::new().validate(sync_ready.as_bytes());
+            let warnings = validator.validate_with_warnings(role_switch.as_bytes())
+                .expect("Expected the message to be valid");
 
-            assert!(validation_result.is_ok())
+            assert!(warnings.is_empty())
         }
 
+        #[cfg(not(feature = "compact-errors"))]
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+        fn strict_mode_promotes_warnings_to_errors() {
+            let validator = UnknownFieldsValidator::new(RoleSwitchValidator::new(), &["senderId", "roleId"]).strict();
 
-            let validation_result = SyncReadyValidator::new().validate(&message);
+            let validation_result = validator.validate(role_switch_with_extra_field().as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::InvalidFormat(
+                ErrorReason::UnknownField, "Unknown field: comment".to_string())))
         }
 
         #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
+        fn errors_of_the_inner_validator_are_reported() {
+            let validator = UnknownFieldsValidator::new(RoleSwitchValidator::new(), &["senderId", "roleId"]);
 
-            let validation_result = SyncReadyValidator::new().validate(message.as_bytes());
+            let validation_result = validator.validate_with_warnings(json::object!{}.dump().as_bytes());
 
             assert!(validation_result.is_err())
         }
+    }
+
+    mod shadow {
+        use std::cell::RefCell;
+
+        use crate::messages::json::{RoleSwitchValidator, ShadowValidator};
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
 
         #[test]
-        fn it_considers_a_sync_ready_without_a_sender_id_invalid() {
-            let sync_ready = json::object!{}.dump();
+        fn invalid_messages_pass_through_and_the_failure_is_observed() {
+            let failures = RefCell::new(Vec::new());
+            let validator = ShadowValidator::new(RoleSwitchValidator::new(), |_: &[u8], error: &AlicaMessageValidationError| {
+                failures.borrow_mut().push(error.kind())
+            });
 
-            let validation_result = SyncReadyValidator::new().validate(sync_ready.as_bytes());
+            let validation_result = validator.validate(json::object!{}.dump().as_bytes());
 
-            assert!(validation_result.is_err())
+            assert!(validation_result.is_ok());
+            assert_eq!(failures.borrow().len(), 1);
         }
 
         #[test]
-        fn it_considers_a_sync_ready_without_a_synchronisation_id_invalid() {
-            let sync_ready = json::object!{
+        fn valid_messages_are_not_observed() {
+            let failures = RefCell::new(Vec::new());
+            let validator = ShadowValidator::new(RoleSwitchValidator::new(), |_: &[u8], error: &AlicaMessageValidationError| {
+                failures.borrow_mut().push(error.kind())
+            });
+            let role_switch = json::object!{
                 senderId: {
                     type: 0,
                     value: "id"
-                }
+                },
+                roleId: 1
             }.dump();
 
-            let validation_result = SyncReadyValidator::new().validate(sync_ready.as_bytes());
+            let validation_result = validator.validate(role_switch.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert!(validation_result.is_ok());
+            assert!(failures.borrow().is_empty());
         }
     }
 
-    mod sync_talk {
-        use crate::messages::json::SyncTalkValidator;
+    mod array_root {
+        use crate::messages::json::{ArrayRootValidator, CapnZeroIdValidator};
         use crate::messages::AlicaMessageJsonValidator;
 
         #[test]
-        fn it_considers_a_complete_sync_talk_valid() {
-            let sync_talk = json::object!{
-                senderId: {
+        fn it_considers_an_array_of_valid_entries_valid() {
+            let ids = json::array![
+                {
                     type: 0,
                     value: "id"
                 },
-                syncData: [
-                    {
-                        robotId: {
-                            type: 1,
-                            value: "robot1"
-                        },
-                        transitionId: 1,
-                        transitionHolds: false,
-                        ack: true
-                    },
-                    {
-                        robotId: {
-                            type: 1,
-                            value: "robot2"
-                        },
-                        transitionId: 2,
-                        transitionHolds: true,
-                        ack: false
-                    },
-                ]
-            }.dump();
+                {
+                    type: 1,
+                    value: "other id"
+                }
+            ].dump();
 
-            let validation_result = SyncTalkValidator::new().validate(sync_talk.as_bytes());
+            let validation_result = ArrayRootValidator::new(CapnZeroIdValidator::new()).validate(ids.as_bytes());
 
             assert!(validation_result.is_ok())
         }
 
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+        fn it_considers_an_array_with_an_invalid_entry_invalid() {
+            let ids = json::array![
+                {
+                    type: 0,
+                    value: "id"
+                },
+                {
+                    type: 7
+                }
+            ].dump();
 
-            let validation_result = SyncTalkValidator::new().validate(&message);
+            let validation_result = ArrayRootValidator::new(CapnZeroIdValidator::new()).validate(ids.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
+        fn it_considers_an_object_root_invalid() {
+            let id = json::object!{
+                type: 0,
+                value: "id"
+            }.dump();
 
-            let validation_result = SyncTalkValidator::new().validate(message.as_bytes());
+            let validation_result = ArrayRootValidator::new(CapnZeroIdValidator::new()).validate(id.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_a_sync_talk_without_a_sender_id_invalid() {
-            let sync_talk = json::object!{}.dump();
-
-            let validation_result = SyncTalkValidator::new().validate(sync_talk.as_bytes());
+        fn it_considers_a_non_json_message_invalid() {
+            let validation_result = ArrayRootValidator::new(CapnZeroIdValidator::new()).validate("[".as_bytes());
 
             assert!(validation_result.is_err())
         }
+    }
+
+    mod versioned {
+        use crate::messages::json::{helper, validation, ClosureValidator, RoleSwitchValidator, VersionedValidator};
+        use crate::messages::AlicaMessageJsonValidator;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::messages::AlicaMessageValidationError;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
+
+        fn role_switch_validator() -> VersionedValidator {
+            VersionedValidator::new(1)
+                .with_version(1, Box::new(RoleSwitchValidator::new()))
+                .with_version(2, Box::new(ClosureValidator::new(|message: &[u8]| {
+                    let role_switch = helper::parse_object(message)?;
+                    validation::validate_capnzero_id_field(&role_switch, "senderId")?;
+                    validation::validate_string_field(&role_switch, "roleName")
+                })))
+        }
 
         #[test]
-        fn it_considers_a_sync_talk_without_sync_data_invalid() {
-            let sync_talk = json::object!{
+        fn it_validates_a_v1_message_with_the_v1_validator() {
+            let role_switch = json::object!{
+                schemaVersion: 1,
                 senderId: {
                     type: 0,
                     value: "id"
-                }
+                },
+                roleId: 1
             }.dump();
 
-            let validation_result = SyncTalkValidator::new().validate(sync_talk.as_bytes());
+            let validation_result = role_switch_validator().validate(role_switch.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert!(validation_result.is_ok())
         }
-    }
 
-    mod sync_data {
-        use crate::messages::json::SyncDataValidator;
-        use crate::messages::AlicaMessageJsonValidator;
+        #[test]
+        fn it_validates_a_v2_message_with_the_v2_validator() {
+            let role_switch = json::object!{
+                schemaVersion: 2,
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleName: "attacker"
+            }.dump();
+            let v1_shaped_role_switch = json::object!{
+                schemaVersion: 2,
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 1
+            }.dump();
+
+            assert!(role_switch_validator().validate(role_switch.as_bytes()).is_ok());
+            assert!(role_switch_validator().validate(v1_shaped_role_switch.as_bytes()).is_err());
+        }
 
         #[test]
-        fn it_considers_a_complete_sync_data_valid() {
-            let sync_talk = json::object!{
-                robotId: {
+        fn it_uses_the_default_version_without_a_schema_version() {
+            let role_switch = json::object!{
+                senderId: {
                     type: 0,
                     value: "id"
                 },
-                transitionId: 1,
-                transitionHolds: true,
-                ack: true
+                roleId: 1
             }.dump();
 
-            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+            let validation_result = role_switch_validator().validate(role_switch.as_bytes());
 
             assert!(validation_result.is_ok())
         }
 
+        #[cfg(not(feature = "compact-errors"))]
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+        fn it_rejects_an_unknown_schema_version() {
+            let role_switch = json::object!{
+                schemaVersion: 3,
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 1
+            }.dump();
 
-            let validation_result = SyncDataValidator::new().validate(&message);
+            let validation_result = role_switch_validator().validate(role_switch.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert_eq!(validation_result,
+                       Err(AlicaMessageValidationError::InvalidFormat(ErrorReason::Unsupported, "schemaVersion 3 is not supported".to_string())))
         }
+    }
 
-        #[test]
-        fn it_considers_a_non_json_message_invalid() {
-            let message = "";
-
-            let validation_result = SyncDataValidator::new().validate(message.as_bytes());
+    mod recursive_plan_tree {
+        use crate::messages::json::RecursivePlanTreeValidator;
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationError};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::error::ErrorReason;
 
-            assert!(validation_result.is_err())
+        fn two_level_plan_tree() -> String {
+            json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                stateIds: [1, 2],
+                succeededEps: [],
+                children: [
+                    {
+                        stateIds: [3],
+                        succeededEps: [3]
+                    },
+                    {
+                        stateIds: [4],
+                        succeededEps: [],
+                        children: []
+                    }
+                ]
+            }.dump()
         }
 
         #[test]
-        fn it_considers_a_sync_data_without_robot_id_invalid() {
-            let sync_talk = json::object!{}.dump();
+        fn it_considers_a_nested_plan_tree_valid() {
+            let validation_result = RecursivePlanTreeValidator::new().validate(two_level_plan_tree().as_bytes());
 
-            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+            assert!(validation_result.is_ok())
+        }
 
-            assert!(validation_result.is_err())
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn it_considers_a_plan_tree_exceeding_the_maximum_depth_invalid() {
+            let validation_result = RecursivePlanTreeValidator::new()
+                .with_max_depth(1)
+                .validate(two_level_plan_tree().as_bytes());
+
+            assert_eq!(validation_result,
+                       Err(AlicaMessageValidationError::InvalidFormat(
+                           ErrorReason::OutOfRange, "Plan tree exceeds the maximum depth of 1".to_string())))
         }
 
         #[test]
-        fn it_considers_a_sync_data_without_transition_id_invalid() {
-            let sync_talk = json::object!{
-                robotId: {
+        fn it_considers_a_plan_tree_with_an_invalid_child_invalid() {
+            let plan_tree = json::object!{
+                senderId: {
                     type: 0,
                     value: "id"
-                }
+                },
+                stateIds: [1],
+                succeededEps: [],
+                children: [
+                    {
+                        stateIds: [2]
+                    }
+                ]
             }.dump();
 
-            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+            let validation_result = RecursivePlanTreeValidator::new().validate(plan_tree.as_bytes());
+
+            assert_eq!(validation_result, Err(AlicaMessageValidationError::MissingField("succeededEps".to_string())))
+        }
+
+        #[test]
+        fn it_considers_a_plan_tree_without_a_sender_id_invalid() {
+            let validation_result = RecursivePlanTreeValidator::new().validate(json::object!{}.dump().as_bytes());
 
             assert!(validation_result.is_err())
         }
+    }
+
+    #[cfg(feature = "timing")]
+    mod timing {
+        use crate::messages::AlicaMessageJsonValidator;
+        use std::time::Duration;
+
+        use crate::messages::json::{DurationHistogram, RoleSwitchValidator, SyncReadyValidator, TimedValidator, ValidationTimings};
 
         #[test]
-        fn it_considers_a_sync_data_without_transition_holds_status_invalid() {
-            let sync_talk = json::object!{
-                robotId: {
+        fn validation_durations_are_recorded_per_message_type() {
+            let timings = ValidationTimings::new();
+            let role_switch_validator = TimedValidator::new("RoleSwitch", RoleSwitchValidator::new(), &timings);
+            let sync_ready_validator = TimedValidator::new("SyncReady", SyncReadyValidator::new(), &timings);
+            let role_switch = json::object!{
+                senderId: {
                     type: 0,
                     value: "id"
                 },
-                transitionId: 1
+                roleId: 1
             }.dump();
 
-            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+            role_switch_validator.validate(role_switch.as_bytes()).expect("Expected a valid role switch");
+            role_switch_validator.validate(json::object!{}.dump().as_bytes()).expect_err("Expected an invalid role switch");
+            sync_ready_validator.validate(json::object!{}.dump().as_bytes()).expect_err("Expected an invalid sync ready");
 
-            assert!(validation_result.is_err())
+            let report = timings.report();
+            assert_eq!(report["RoleSwitch"].total(), 2);
+            assert_eq!(report["SyncReady"].total(), 1);
         }
 
         #[test]
-        fn it_considers_a_sync_data_without_ack_status_invalid() {
-            let sync_talk = json::object!{
-                robotId: {
-                    type: 0,
-                    value: "id"
-                },
-                transitionId: 1,
-                transitionHolds: true
-            }.dump();
+        fn durations_are_counted_in_fixed_buckets() {
+            let mut histogram = DurationHistogram::default();
 
-            let validation_result = SyncDataValidator::new().validate(sync_talk.as_bytes());
+            histogram.record(Duration::from_micros(1));
+            histogram.record(Duration::from_micros(5));
+            histogram.record(Duration::from_micros(50));
+            histogram.record(Duration::from_secs(5));
+            histogram.record(Duration::from_secs(50));
 
-            assert!(validation_result.is_err())
+            assert_eq!(histogram.counts(), &[1, 1, 1, 0, 0, 0, 0, 2])
         }
     }
 
-    mod capnzero_id {
-        use crate::messages::json::CapnZeroIdValidator;
+    #[cfg(feature = "json-schema")]
+    mod json_schema {
+        use crate::messages::json::JsonSchemaValidator;
         use crate::messages::AlicaMessageJsonValidator;
 
+        fn heartbeat_validator() -> JsonSchemaValidator {
+            let schema = json::object!{
+                type: "object",
+                properties: {
+                    senderId: { type: "string" },
+                    sequence: { type: "integer", minimum: 0 }
+                },
+                required: ["senderId", "sequence"]
+            }.dump();
+
+            JsonSchemaValidator::from_schema(schema.as_bytes()).expect("Could not compile schema")
+        }
+
         #[test]
-        fn it_considers_a_complete_capnzero_id_valid() {
-            let capnzero_id = json::object!{
-                type: 0,
-                value: "id"
+        fn it_considers_a_message_matching_the_schema_valid() {
+            let heartbeat = json::object!{
+                senderId: "id",
+                sequence: 1
             }.dump();
 
-            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
+            let validation_result = heartbeat_validator().validate(heartbeat.as_bytes());
 
             assert!(validation_result.is_ok())
         }
 
         #[test]
-        fn it_considers_a_non_utf8_message_invalid() {
-            let message = vec![0x0];
+        fn it_considers_a_message_violating_the_schema_invalid() {
+            let heartbeat = json::object!{
+                senderId: "id",
+                sequence: -1
+            }.dump();
 
-            let validation_result = CapnZeroIdValidator::new().validate(&message);
+            let validation_result = heartbeat_validator().validate(heartbeat.as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
         fn it_considers_a_non_json_message_invalid() {
-            let message = "";
-
-            let validation_result = CapnZeroIdValidator::new().validate(message.as_bytes());
+            let validation_result = heartbeat_validator().validate("".as_bytes());
 
             assert!(validation_result.is_err())
         }
 
         #[test]
-        fn it_considers_an_id_without_a_type_invalid() {
-            let capnzero_id = json::object!{}.dump();
+        fn a_message_with_a_byte_order_mark_is_validated() {
+            let heartbeat = format!("\u{feff}{}", json::object!{
+                senderId: "id",
+                sequence: 1
+            }.dump());
 
-            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
+            let validation_result = heartbeat_validator().validate(heartbeat.as_bytes());
 
-            assert!(validation_result.is_err())
+            assert!(validation_result.is_ok())
         }
 
+        #[cfg(not(feature = "compact-errors"))]
         #[test]
-        fn it_considers_an_id_without_a_value_invalid() {
-            let capnzero_id = json::object!{
-                type: 0
+        fn long_schema_violations_are_truncated() {
+            let heartbeat = json::object!{
+                senderId: "id",
+                sequence: "x".repeat(500)
             }.dump();
 
-            let validation_result = CapnZeroIdValidator::new().validate(capnzero_id.as_bytes());
+            let error = heartbeat_validator().validate(heartbeat.as_bytes()).expect_err("Expected an error");
 
-            assert!(validation_result.is_err())
+            assert!(error.to_string().ends_with("..."))
+        }
+
+        #[test]
+        fn an_invalid_schema_is_rejected() {
+            let schema = json::object!{
+                type: 1
+            }.dump();
+
+            let result = JsonSchemaValidator::from_schema(schema.as_bytes());
+
+            assert!(result.is_err())
         }
     }
-}
\ No newline at end of file
+}
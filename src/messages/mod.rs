@@ -1,24 +1,370 @@
+use std::fmt::{Display, Formatter};
 use mockall;
 
+use crate::error::ErrorReason;
+
 pub mod json;
+pub mod registry;
 
+#[derive(PartialEq, Debug)]
 pub enum AlicaMessageValidationError {
-    InvalidFormat(String),
-    MissingField(String)
+    InvalidFormat(ErrorReason, String),
+    MissingField(String),
+    UnknownMessageType(String)
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum ErrorKind {
+    Invalid,
+    Missing,
+    UnknownMessageType
+}
+
+impl AlicaMessageValidationError {
+    pub fn field(&self) -> Option<&str> {
+        match self {
+            AlicaMessageValidationError::MissingField(field) => Some(field),
+            _ => None
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            AlicaMessageValidationError::InvalidFormat(..) => ErrorKind::Invalid,
+            AlicaMessageValidationError::MissingField(_) => ErrorKind::Missing,
+            AlicaMessageValidationError::UnknownMessageType(_) => ErrorKind::UnknownMessageType
+        }
+    }
+
+    pub fn reason(&self) -> ErrorReason {
+        match self {
+            AlicaMessageValidationError::InvalidFormat(reason, _) => *reason,
+            AlicaMessageValidationError::MissingField(_) => ErrorReason::MissingField,
+            AlicaMessageValidationError::UnknownMessageType(_) => ErrorReason::UnknownMessageType
+        }
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl Display for AlicaMessageValidationError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(formatter, "E{}", self.reason().code())
+    }
+}
+
+#[cfg(not(feature = "compact-errors"))]
+impl Display for AlicaMessageValidationError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlicaMessageValidationError::InvalidFormat(_, message) => write!(formatter, "{}", message),
+            AlicaMessageValidationError::MissingField(field) => write!(formatter, "Required field missing: {}", field),
+            AlicaMessageValidationError::UnknownMessageType(message_type) =>
+                write!(formatter, "Unknown message type: {}", message_type)
+        }
+    }
 }
 
+impl std::error::Error for AlicaMessageValidationError {}
+
 impl Into<String> for AlicaMessageValidationError {
     fn into(self) -> String {
+        self.to_string()
+    }
+}
+
+pub type AlicaMessageValidationResult = Result<(), AlicaMessageValidationError>;
+
+#[derive(PartialEq, Debug)]
+pub enum Warning {
+    UnknownField(String)
+}
+
+impl Display for Warning {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            AlicaMessageValidationError::InvalidFormat(message) => message,
-            AlicaMessageValidationError::MissingField(field) => format!("Required field missing: {}", field)
+            Warning::UnknownField(field) => write!(formatter, "Unknown field: {}", field)
         }
     }
 }
 
-pub type AlicaMessageValidationResult = Result<(), AlicaMessageValidationError>;
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum MessageType {
+    AlicaEngineInfo,
+    AllocationAuthorityInfo,
+    EntryPointRobot,
+    PlanTreeInfo,
+    RoleSwitch,
+    SolverResult,
+    SolverVar,
+    SyncReady,
+    SyncTalk,
+    SyncData,
+    CapnZeroId,
+    Bid,
+    Pose
+}
+
+impl MessageType {
+    pub const ALL: [MessageType; 13] = [
+        MessageType::AlicaEngineInfo,
+        MessageType::AllocationAuthorityInfo,
+        MessageType::EntryPointRobot,
+        MessageType::PlanTreeInfo,
+        MessageType::RoleSwitch,
+        MessageType::SolverResult,
+        MessageType::SolverVar,
+        MessageType::SyncReady,
+        MessageType::SyncTalk,
+        MessageType::SyncData,
+        MessageType::CapnZeroId,
+        MessageType::Bid,
+        MessageType::Pose,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MessageType::AlicaEngineInfo => "AlicaEngineInfo",
+            MessageType::AllocationAuthorityInfo => "AllocationAuthorityInfo",
+            MessageType::EntryPointRobot => "EntryPointRobot",
+            MessageType::PlanTreeInfo => "PlanTreeInfo",
+            MessageType::RoleSwitch => "RoleSwitch",
+            MessageType::SolverResult => "SolverResult",
+            MessageType::SolverVar => "SolverVar",
+            MessageType::SyncReady => "SyncReady",
+            MessageType::SyncTalk => "SyncTalk",
+            MessageType::SyncData => "SyncData",
+            MessageType::CapnZeroId => "CapnZeroId",
+            MessageType::Bid => "Bid",
+            MessageType::Pose => "Pose"
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        MessageType::ALL.iter().copied().find(|message_type| message_type.name() == name)
+    }
+}
 
 #[mockall::automock]
 pub trait AlicaMessageJsonValidator {
     fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult;
-}
\ No newline at end of file
+
+    fn validate_object(&self, message: &::json::object::Object) -> AlicaMessageValidationResult {
+        self.validate(::json::JsonValue::Object(message.clone()).dump().as_bytes())
+    }
+
+    fn validate_with_warnings(&self, message: &[u8]) -> Result<Vec<Warning>, AlicaMessageValidationError> {
+        self.validate(message).map(|_| Vec::new())
+    }
+
+    fn validate_all(&self, message: &[u8]) -> Result<(), Vec<AlicaMessageValidationError>> {
+        self.validate(message).map_err(|error| vec![error])
+    }
+
+    #[cfg(feature = "serde")]
+    fn validate_value(&self, message: &serde_json::Value) -> AlicaMessageValidationResult {
+        if let ::json::JsonValue::Object(object) = json::helper::from_serde_value(message) {
+            return self.validate_object(&object);
+        }
+        let message_bytes = serde_json::to_vec(message)
+            .map_err(|_| AlicaMessageValidationError::InvalidFormat(ErrorReason::Malformed,
+                                                                    crate::helper::error_text!("Message is no JSON structure")))?;
+        self.validate(&message_bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::messages::{AlicaMessageValidationError, ErrorKind, MessageType};
+    use crate::error::ErrorReason;
+    use crate::messages::json::all_validators;
+
+    #[test]
+    fn every_built_in_validator_has_a_message_type() {
+        let message_types: Vec<&str> = all_validators().into_iter().map(|(message_type, _)| message_type).collect();
+
+        assert_eq!(message_types, MessageType::ALL.iter().map(|message_type| message_type.name()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn message_types_are_looked_up_by_name() {
+        assert_eq!(MessageType::from_name("RoleSwitch"), Some(MessageType::RoleSwitch));
+        assert_eq!(MessageType::from_name("roleswitch"), None);
+    }
+
+    #[test]
+    fn missing_fields_expose_their_name_and_kind() {
+        let error = AlicaMessageValidationError::MissingField("senderId".to_string());
+
+        assert_eq!(error.field(), Some("senderId"));
+        assert_eq!(error.kind(), ErrorKind::Missing);
+    }
+
+    #[test]
+    fn invalid_formats_expose_no_field() {
+        let error = AlicaMessageValidationError::InvalidFormat(ErrorReason::WrongType, "senderId is no string".to_string());
+
+        assert_eq!(error.field(), None);
+        assert_eq!(error.kind(), ErrorKind::Invalid);
+    }
+
+    #[test]
+    fn unknown_message_types_expose_their_kind() {
+        let error = AlicaMessageValidationError::UnknownMessageType("Bogus".to_string());
+
+        assert_eq!(error.kind(), ErrorKind::UnknownMessageType)
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn missing_fields_are_described_by_their_name() {
+        let message: String = AlicaMessageValidationError::MissingField("senderId".to_string()).into();
+
+        assert_eq!(message, "Required field missing: senderId")
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn unknown_message_types_are_described_by_their_name() {
+        let message: String = AlicaMessageValidationError::UnknownMessageType("Bogus".to_string()).into();
+
+        assert_eq!(message, "Unknown message type: Bogus")
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationResult};
+        use crate::messages::json::{helper, validation, AlicaEngineInfoValidator, PlanTreeInfoValidator};
+
+        #[test]
+        fn an_alica_engine_info_can_be_validated_as_serde_value() {
+            let engine_info = serde_json::json!({
+                "senderId": {
+                    "type": 0,
+                    "value": "id"
+                },
+                "masterPlan": "master plan",
+                "currentPlan": "current plan",
+                "currentState": "current state",
+                "currentRole": "current role",
+                "currentTask": "current task",
+                "agentIdsWithMe": []
+            });
+
+            let validation_result = AlicaEngineInfoValidator::new().validate_value(&engine_info);
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn an_incomplete_alica_engine_info_is_invalid_as_serde_value() {
+            let engine_info = serde_json::json!({});
+
+            let validation_result = AlicaEngineInfoValidator::new().validate_value(&engine_info);
+
+            assert!(validation_result.is_err())
+        }
+
+        #[test]
+        fn a_serde_value_is_validated_without_serializing_it() {
+            let plan_tree_info = serde_json::json!({
+                "senderId": {
+                    "type": 0,
+                    "value": "id"
+                },
+                "stateIds": [1, -2],
+                "succeededEps": []
+            });
+
+            let validation_result = WithoutBytes(PlanTreeInfoValidator::new()).validate_value(&plan_tree_info);
+
+            assert!(validation_result.is_ok())
+        }
+
+        #[test]
+        fn a_validator_without_object_support_receives_the_serialized_value() {
+            let task = serde_json::json!({
+                "taskId": 1
+            });
+
+            let validation_result = TaskValidator {}.validate_value(&task);
+
+            assert!(validation_result.is_ok())
+        }
+
+        struct WithoutBytes<V>(V);
+
+        impl<V: AlicaMessageJsonValidator> AlicaMessageJsonValidator for WithoutBytes<V> {
+            fn validate(&self, _: &[u8]) -> AlicaMessageValidationResult {
+                panic!("The message was serialized")
+            }
+
+            fn validate_object(&self, message: &json::object::Object) -> AlicaMessageValidationResult {
+                self.0.validate_object(message)
+            }
+        }
+
+        struct TaskValidator {}
+
+        impl AlicaMessageJsonValidator for TaskValidator {
+            fn validate(&self, message: &[u8]) -> AlicaMessageValidationResult {
+                validation::validate_integer_field(&helper::parse_object(message)?, "taskId")
+            }
+        }
+    }
+
+    #[cfg(feature = "compact-errors")]
+    mod compact_errors {
+        use crate::messages::json::RoleSwitchValidator;
+        use crate::error::ErrorReason;
+        use crate::messages::{AlicaMessageJsonValidator, ErrorKind};
+
+        #[test]
+        fn missing_fields_and_wrong_types_are_distinguished_by_code() {
+            let role_switch_without_role_id = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                }
+            }.dump();
+            let role_switch_with_string_role_id = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: "1"
+            }.dump();
+
+            let missing_field_error = RoleSwitchValidator::new().validate(role_switch_without_role_id.as_bytes())
+                .expect_err("Expected a missing field error");
+            let wrong_type_error = RoleSwitchValidator::new().validate(role_switch_with_string_role_id.as_bytes())
+                .expect_err("Expected an invalid format error");
+
+            assert_eq!(missing_field_error.kind(), ErrorKind::Missing);
+            assert_eq!(wrong_type_error.kind(), ErrorKind::Invalid);
+            assert_eq!(missing_field_error.reason(), ErrorReason::MissingField);
+            assert_eq!(wrong_type_error.reason(), ErrorReason::WrongType);
+            assert_eq!(missing_field_error.to_string(), "E1");
+            assert_eq!(wrong_type_error.to_string(), "E2");
+        }
+
+        #[test]
+        fn invalid_formats_are_distinguished_by_their_reason() {
+            let role_switch_with_negative_role_id = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: -1
+            }.dump();
+
+            let out_of_range_error = RoleSwitchValidator::new().validate(role_switch_with_negative_role_id.as_bytes())
+                .expect_err("Expected an invalid format error");
+            let malformed_error = RoleSwitchValidator::new().validate("{".as_bytes())
+                .expect_err("Expected an invalid format error");
+
+            assert_eq!(out_of_range_error.kind(), malformed_error.kind());
+            assert_eq!(out_of_range_error.to_string(), "E3");
+            assert_eq!(malformed_error.to_string(), "E5");
+        }
+    }
+}
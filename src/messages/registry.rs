@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+
+use crate::messages::{AlicaMessageJsonValidator, AlicaMessageValidationResult};
+use crate::messages::json::all_validators;
+use crate::helper;
+use crate::messages::AlicaMessageValidationError::UnknownMessageType;
+
+#[derive(Default)]
+pub struct ValidatorRegistry {
+    validators: BTreeMap<String, Box<dyn AlicaMessageJsonValidator>>,
+    case_insensitive: bool
+}
+
+impl ValidatorRegistry {
+    pub fn new() -> Self {
+        ValidatorRegistry {
+            validators: BTreeMap::new(),
+            case_insensitive: false
+        }
+    }
+
+    pub fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self.validators = std::mem::take(&mut self.validators).into_iter()
+            .map(|(message_type, validator)| (message_type.to_lowercase(), validator))
+            .collect();
+        self
+    }
+
+    pub fn with_defaults() -> Self {
+        let mut registry = ValidatorRegistry::new();
+        for (message_type, validator) in all_validators() {
+            registry.register(message_type, validator);
+        }
+        registry
+    }
+
+    pub fn register(&mut self, message_type: &str, validator: Box<dyn AlicaMessageJsonValidator>) {
+        self.validators.insert(self.normalize(message_type), validator);
+    }
+
+    pub fn validate(&self, message_type: &str, message: &[u8]) -> AlicaMessageValidationResult {
+        match self.validators.get(&self.normalize(message_type)) {
+            Some(validator) => validator.validate(message),
+            None => Err(UnknownMessageType(helper::truncate_for_error(message_type)))
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.validators.keys().map(|message_type| message_type.as_str())
+    }
+
+    fn normalize(&self, message_type: &str) -> String {
+        if self.case_insensitive {
+            message_type.to_lowercase()
+        } else {
+            message_type.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::messages::registry::ValidatorRegistry;
+    use crate::messages::json::{helper, validation, ClosureValidator, RoleSwitchValidator};
+    use crate::messages::AlicaMessageValidationError;
+
+    #[test]
+    fn messages_are_validated_by_the_validator_registered_for_their_type() {
+        let mut registry = ValidatorRegistry::new();
+        registry.register("RoleSwitch", Box::new(RoleSwitchValidator::new()));
+        let role_switch = json::object!{
+            senderId: {
+                type: 0,
+                value: "id"
+            },
+            roleId: 1
+        }.dump();
+
+        let validation_result = registry.validate("RoleSwitch", role_switch.as_bytes());
+
+        assert!(validation_result.is_ok())
+    }
+
+    #[test]
+    fn invalid_messages_are_rejected() {
+        let mut registry = ValidatorRegistry::new();
+        registry.register("RoleSwitch", Box::new(RoleSwitchValidator::new()));
+
+        let validation_result = registry.validate("RoleSwitch", json::object!{}.dump().as_bytes());
+
+        assert!(validation_result.is_err())
+    }
+
+    #[test]
+    fn messages_of_unregistered_types_are_rejected() {
+        let registry = ValidatorRegistry::with_defaults();
+
+        let validation_result = registry.validate("Bogus", json::object!{}.dump().as_bytes());
+
+        assert_eq!(validation_result, Err(AlicaMessageValidationError::UnknownMessageType("Bogus".to_string())))
+    }
+
+    #[test]
+    fn the_default_registry_knows_bids() {
+        let registry = ValidatorRegistry::with_defaults();
+        let bid = json::object!{
+            senderId: {
+                type: 0,
+                value: "id"
+            },
+            taskId: 3,
+            utility: 0.75
+        }.dump();
+
+        let validation_result = registry.validate("Bid", bid.as_bytes());
+
+        assert!(validation_result.is_ok())
+    }
+
+    #[test]
+    fn the_default_registry_knows_poses() {
+        let registry = ValidatorRegistry::with_defaults();
+        let pose = json::object!{
+            x: 1.5,
+            y: -2.0,
+            theta: 0.25
+        }.dump();
+
+        let validation_result = registry.validate("Pose", pose.as_bytes());
+
+        assert!(validation_result.is_ok())
+    }
+
+    #[test]
+    fn the_default_registry_knows_the_built_in_message_types() {
+        let registry = ValidatorRegistry::with_defaults();
+        let role_switch = json::object!{
+            senderId: {
+                type: 0,
+                value: "id"
+            },
+            roleId: 1
+        }.dump();
+
+        let validation_result = registry.validate("RoleSwitch", role_switch.as_bytes());
+
+        assert!(validation_result.is_ok())
+    }
+
+    #[test]
+    fn registries_built_in_the_same_order_iterate_identically() {
+        let registry = ValidatorRegistry::with_defaults();
+        let other_registry = ValidatorRegistry::with_defaults();
+
+        let message_types: Vec<&str> = registry.iter().collect();
+        let other_message_types: Vec<&str> = other_registry.iter().collect();
+
+        assert_eq!(message_types.len(), 13);
+        assert_eq!(message_types, other_message_types);
+    }
+
+    #[test]
+    fn message_types_are_iterated_in_sorted_order() {
+        let mut registry = ValidatorRegistry::new();
+        registry.register("SyncReady", Box::new(RoleSwitchValidator::new()));
+        registry.register("RoleSwitch", Box::new(RoleSwitchValidator::new()));
+
+        let message_types: Vec<&str> = registry.iter().collect();
+
+        assert_eq!(message_types, vec!["RoleSwitch", "SyncReady"])
+    }
+
+    #[test]
+    fn message_types_are_case_sensitive_by_default() {
+        let mut registry = ValidatorRegistry::new();
+        registry.register("RoleSwitch", Box::new(RoleSwitchValidator::new()));
+
+        let validation_result = registry.validate("roleswitch", json::object!{}.dump().as_bytes());
+
+        assert_eq!(validation_result, Err(AlicaMessageValidationError::UnknownMessageType("roleswitch".to_string())))
+    }
+
+    #[test]
+    fn message_types_can_be_dispatched_case_insensitively() {
+        let mut registry = ValidatorRegistry::new().case_insensitive();
+        registry.register("RoleSwitch", Box::new(RoleSwitchValidator::new()));
+        let role_switch = json::object!{
+            senderId: {
+                type: 0,
+                value: "id"
+            },
+            roleId: 1
+        }.dump();
+
+        assert!(registry.validate("roleswitch", role_switch.as_bytes()).is_ok());
+        assert!(registry.validate("rolESwitch", role_switch.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn closures_can_be_registered_as_validators() {
+        let mut registry = ValidatorRegistry::new();
+        registry.register("Heartbeat", Box::new(ClosureValidator::new(|message: &[u8]| {
+            let heartbeat = helper::parse_object(message)?;
+            validation::validate_capnzero_id_field(&heartbeat, "senderId")
+        })));
+        let heartbeat = json::object!{
+            senderId: {
+                type: 0,
+                value: "id"
+            }
+        }.dump();
+
+        assert!(registry.validate("Heartbeat", heartbeat.as_bytes()).is_ok());
+        assert!(registry.validate("Heartbeat", json::object!{}.dump().as_bytes()).is_err());
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn oversized_unknown_message_types_are_truncated_in_errors() {
+        let registry = ValidatorRegistry::with_defaults();
+        let message_type = "Bogus".repeat(10_000);
+
+        let error = registry.validate(&message_type, json::object!{}.dump().as_bytes())
+            .expect_err("Expected an unknown message type error");
+
+        let message: String = error.into();
+        assert!(message.len() < 100);
+        assert!(message.ends_with("..."));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::payloads::{Error, Format, TransactionPayload};
+
+pub struct BatchBuilder<'a> {
+    format: &'a dyn Format,
+    max_batch_size: usize,
+    batch_size: usize,
+    serialized_payloads: Vec<Vec<u8>>
+}
+
+impl<'a> BatchBuilder<'a> {
+    pub fn new(format: &'a dyn Format, max_batch_size: usize) -> Self {
+        BatchBuilder {
+            format,
+            max_batch_size,
+            batch_size: 0,
+            serialized_payloads: Vec::new()
+        }
+    }
+
+    pub fn try_push(&mut self, payload: &TransactionPayload) -> Result<bool, Error> {
+        let serialized_payload = self.format.serialize(payload)?;
+        if self.batch_size + serialized_payload.len() > self.max_batch_size {
+            return Ok(false);
+        }
+
+        self.batch_size += serialized_payload.len();
+        self.serialized_payloads.push(serialized_payload);
+        Ok(true)
+    }
+
+    pub fn size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn finish(self) -> Vec<Vec<u8>> {
+        self.serialized_payloads
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::payloads::batch::BatchBuilder;
+    use crate::payloads::{pipe_separated, TransactionPayload};
+
+    #[test]
+    fn it_accepts_payloads_up_to_the_max_batch_size() {
+        let format = pipe_separated::Format::default();
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let mut builder = BatchBuilder::new(&format, 26);
+
+        assert!(builder.try_push(&payload).expect("Could not serialize payload"));
+        assert!(builder.try_push(&payload).expect("Could not serialize payload"));
+        assert_eq!(builder.size(), 26);
+        assert_eq!(builder.finish().len(), 2)
+    }
+
+    #[test]
+    fn it_rejects_payloads_exceeding_the_max_batch_size() {
+        let format = pipe_separated::Format::default();
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let oversized_payload = TransactionPayload::new("id", "type", "a much longer message".as_bytes(), 1);
+        let mut builder = BatchBuilder::new(&format, 26);
+        builder.try_push(&payload).expect("Could not serialize payload");
+
+        let accepted = builder.try_push(&oversized_payload).expect("Could not serialize payload");
+
+        assert!(!accepted);
+        assert_eq!(builder.size(), 13);
+        assert_eq!(builder.finish(), vec!["id|type|msg|1".as_bytes().to_vec()])
+    }
+}
@@ -0,0 +1,107 @@
+use sha2::Digest;
+
+use crate::payloads::{Error, ParsingResult, TransactionPayload, SerializationResult};
+use crate::payloads;
+use crate::helper::error_text;
+use crate::error::ErrorReason;
+
+pub const CHECKSUM_LENGTH: usize = 4;
+
+pub struct Format<F: payloads::Format> {
+    format: F
+}
+
+impl<F: payloads::Format> Format<F> {
+    pub fn new(format: F) -> Self {
+        Format {
+            format
+        }
+    }
+
+    fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+        let digest = sha2::Sha256::digest(bytes);
+        let mut checksum = [0; CHECKSUM_LENGTH];
+        checksum.copy_from_slice(&digest[..CHECKSUM_LENGTH]);
+        checksum
+    }
+}
+
+impl<F: payloads::Format + Default> Default for Format<F> {
+    fn default() -> Self {
+        Format::new(F::default())
+    }
+}
+
+impl<F: payloads::Format> payloads::Format for Format<F> {
+    fn serialize(&self, payload: &TransactionPayload) -> SerializationResult {
+        let mut output = self.format.serialize(payload)?;
+        let checksum = Self::checksum(&output);
+        output.extend_from_slice(&checksum);
+        Ok(output)
+    }
+
+    fn serialize_batch(&self, payloads: &[TransactionPayload]) -> SerializationResult {
+        payloads::log::serialize_length_prefixed(self, payloads)
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> ParsingResult {
+        if bytes.len() < CHECKSUM_LENGTH {
+            return Err(Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no checksum")));
+        }
+        let (inner_bytes, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+        if Self::checksum(inner_bytes) != checksum {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("checksum mismatch")));
+        }
+
+        self.format.deserialize(inner_bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::payloads::{checked, pipe_separated, Error, Format, TransactionPayload};
+
+    #[test]
+    fn serialized_payloads_can_be_read_by_parser() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let format = checked::Format::<pipe_separated::Format>::default();
+
+        let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+        let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[test]
+    fn serialized_payloads_end_with_the_checksum() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let format = checked::Format::new(pipe_separated::Format::default());
+
+        let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+
+        assert_eq!(serialized_payload.len(), "id|type|msg|1".len() + checked::CHECKSUM_LENGTH);
+        assert!(serialized_payload.starts_with("id|type|msg|1".as_bytes()));
+    }
+
+    #[test]
+    fn a_flipped_bit_is_detected() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let format = checked::Format::new(pipe_separated::Format::default());
+        let mut serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+        serialized_payload[8] ^= 0x01;
+
+        let result = format.deserialize(&serialized_payload);
+
+        match result {
+            Err(Error::InvalidPayload(_, _)) => (),
+            _ => panic!("Expected an invalid payload error")
+        }
+    }
+
+    #[test]
+    fn payloads_shorter_than_the_checksum_are_rejected() {
+        let result = checked::Format::new(pipe_separated::Format::default()).deserialize(&[0x00]);
+
+        assert!(result.is_err())
+    }
+}
@@ -0,0 +1,223 @@
+use crate::payloads::{decode_message, encode_message, Error, ParsingResult, TransactionPayload, SerializationResult};
+use crate::payloads;
+use crate::helper::error_text;
+use crate::error::ErrorReason;
+
+const BASE64_MESSAGE_ENCODING: &str = "base64";
+const INLINE_MESSAGE_ENCODING: &str = "json";
+
+#[derive(Default)]
+pub struct Format {
+    inline_json_messages: bool
+}
+
+impl Format {
+    pub fn new() -> Self {
+        Format {
+            inline_json_messages: false
+        }
+    }
+
+    pub fn with_inline_json_messages(mut self) -> Self {
+        self.inline_json_messages = true;
+        self
+    }
+
+    fn inline_message(&self, message_bytes: &[u8]) -> Option<::json::JsonValue> {
+        if !self.inline_json_messages {
+            return None;
+        }
+
+        // Only messages in the canonical form of the json crate survive an inline round trip byte for byte
+        let message = std::str::from_utf8(message_bytes).ok()?;
+        ::json::parse(message).ok().filter(|value| value.dump() == message)
+    }
+}
+
+impl payloads::Format for Format {
+    fn serialize(&self, payload: &TransactionPayload) -> SerializationResult {
+        let mut output = ::json::object!{
+            agent_id: payload.agent_id.as_str(),
+            message_type: payload.message_type.as_str(),
+            message: encode_message(&payload.message_bytes),
+            timestamp: payload.timestamp
+        };
+        if let Some(family_name) = &payload.family_name {
+            payloads::validate_family_name(family_name)?;
+            output["family_name"] = family_name.as_str().into();
+        }
+        if let Some(message) = self.inline_message(&payload.message_bytes) {
+            output["message"] = message;
+            output["message_encoding"] = INLINE_MESSAGE_ENCODING.into();
+        } else if self.inline_json_messages {
+            output["message_encoding"] = BASE64_MESSAGE_ENCODING.into();
+        }
+        let output = output.dump();
+        Ok(output.as_bytes().to_vec())
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> ParsingResult {
+        let payload = std::str::from_utf8(bytes)
+            .map_err(|_| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload is not a string")))?;
+        let payload = ::json::parse(payload)
+            .map_err(|_| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload is no JSON structure")))?;
+        if !payload.is_object() {
+            return Err(Error::InvalidPayload(ErrorReason::WrongType, error_text!("Root of payload is no object")));
+        }
+
+        let agent_id = payload["agent_id"].as_str()
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no agent id")))?;
+        let message_type = payload["message_type"].as_str()
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no message type")))?;
+        let message_bytes = match payload["message_encoding"].as_str().unwrap_or(BASE64_MESSAGE_ENCODING) {
+            BASE64_MESSAGE_ENCODING => payload["message"].as_str()
+                .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no message")))
+                .and_then(decode_message)?,
+            INLINE_MESSAGE_ENCODING => match &payload["message"] {
+                ::json::JsonValue::Null => return Err(Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no message"))),
+                message => message.dump().into_bytes()
+            },
+            _ => return Err(Error::InvalidPayload(ErrorReason::Unsupported, error_text!("Payload has an unknown message encoding")))
+        };
+        let timestamp = match &payload["timestamp"] {
+            ::json::JsonValue::Null => Err(Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no timestamp"))),
+            timestamp => timestamp.as_u64().ok_or(Error::InvalidTimestamp)
+        }?;
+        let family_name = match &payload["family_name"] {
+            ::json::JsonValue::Null => None,
+            family_name => {
+                let family_name = family_name.as_str()
+                    .ok_or_else(|| Error::InvalidPayload(ErrorReason::WrongType, error_text!("Payload family name is no string")))?;
+                payloads::validate_family_name(family_name)?;
+                Some(family_name.to_string())
+            }
+        };
+
+        Ok(TransactionPayload {
+            family_name,
+            ..TransactionPayload::new(
+                agent_id,
+                message_type,
+                &message_bytes,
+                timestamp,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::payloads::{json, Format, TransactionPayload};
+
+    #[test]
+    fn serialized_payloads_can_be_read_by_parser() {
+        let transaction_payload = TransactionPayload::new("id", "type", &[0x00, 0xff, b'|'], 684948894984);
+
+        let serialized_payload = json::Format::default().serialize(&transaction_payload)
+            .expect("Could not serialize payload");
+        let result = json::Format::default().deserialize(&serialized_payload)
+            .expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[test]
+    fn serialized_payloads_contain_the_encoded_message() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+        let serialized_payload = json::Format::default().serialize(&transaction_payload)
+            .expect("Could not serialize payload");
+
+        assert_eq!(String::from_utf8(serialized_payload).unwrap(),
+                   r#"{"agent_id":"id","message_type":"type","message":"bXNn","timestamp":1}"#)
+    }
+
+    #[test]
+    fn payloads_without_a_timestamp_are_not_parsed() {
+        let payload = r#"{"agent_id":"id","message_type":"type","message":"bXNn"}"#;
+
+        let result = json::Format::default().deserialize(payload.as_bytes());
+
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn payloads_with_an_invalid_timestamp_are_not_parsed() {
+        let payload = r#"{"agent_id":"id","message_type":"type","message":"bXNn","timestamp":-1}"#;
+
+        let result = json::Format::default().deserialize(payload.as_bytes());
+
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn payloads_with_a_family_name_survive_a_round_trip() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1).with_family_name("alica");
+
+        let serialized_payload = json::Format::default().serialize(&transaction_payload)
+            .expect("Could not serialize payload");
+        let result = json::Format::default().deserialize(&serialized_payload)
+            .expect("Could not parse payload");
+
+        assert_eq!(String::from_utf8(serialized_payload).unwrap(),
+                   r#"{"agent_id":"id","message_type":"type","message":"bXNn","timestamp":1,"family_name":"alica"}"#);
+        assert_eq!(result, transaction_payload);
+    }
+
+    #[test]
+    fn payloads_with_an_invalid_family_name_are_not_parsed() {
+        let payload = r#"{"agent_id":"id","message_type":"type","message":"bXNn","timestamp":1,"family_name":1}"#;
+
+        let result = json::Format::default().deserialize(payload.as_bytes());
+
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn non_json_payloads_are_not_parsed() {
+        let result = json::Format::default().deserialize("id|type|msg|1".as_bytes());
+
+        assert!(result.is_err())
+    }
+
+    mod inline_messages {
+        use crate::payloads::{json, Format, TransactionPayload};
+
+        #[test]
+        fn json_messages_are_stored_inline() {
+            let format = json::Format::default().with_inline_json_messages();
+            let transaction_payload = TransactionPayload::new("id", "type", r#"{"roleId":1}"#.as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+            assert_eq!(String::from_utf8(serialized_payload).unwrap(),
+                       r#"{"agent_id":"id","message_type":"type","message":{"roleId":1},"timestamp":1,"message_encoding":"json"}"#);
+            assert_eq!(result, transaction_payload);
+        }
+
+        #[test]
+        fn binary_messages_are_stored_base64_encoded() {
+            let format = json::Format::default().with_inline_json_messages();
+            let transaction_payload = TransactionPayload::new("id", "type", &[0x00, 0xff], 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+            assert_eq!(String::from_utf8(serialized_payload).unwrap(),
+                       r#"{"agent_id":"id","message_type":"type","message":"AP8=","timestamp":1,"message_encoding":"base64"}"#);
+            assert_eq!(result, transaction_payload);
+        }
+
+        #[test]
+        fn non_canonical_json_messages_are_stored_base64_encoded() {
+            let format = json::Format::default().with_inline_json_messages();
+            let transaction_payload = TransactionPayload::new("id", "type", r#"{ "roleId": 1 }"#.as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+            assert_eq!(result, transaction_payload)
+        }
+    }
+}
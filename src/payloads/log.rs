@@ -0,0 +1,204 @@
+use std::convert::TryFrom;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::payloads::{Error, Format, ParsingResult, SerializationResult, TransactionPayload};
+use crate::helper::error_text;
+use crate::error::ErrorReason;
+
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+fn length_prefix(record: &[u8]) -> Result<[u8; LENGTH_PREFIX_SIZE], Error> {
+    u32::try_from(record.len())
+        .map(u32::to_be_bytes)
+        .map_err(|_| Error::InvalidPayload(ErrorReason::OutOfRange, error_text!("Payload is too large for a log record")))
+}
+
+pub fn serialize_length_prefixed<F: Format + ?Sized>(format: &F, payloads: &[TransactionPayload]) -> SerializationResult {
+    let mut output = Vec::with_capacity(payloads.iter()
+        .map(|payload| LENGTH_PREFIX_SIZE + payload.estimated_serialized_len())
+        .sum());
+    for payload in payloads {
+        let record = format.serialize(payload)?;
+        output.extend_from_slice(&length_prefix(&record)?);
+        output.extend_from_slice(&record);
+    }
+    Ok(output)
+}
+
+pub fn split_length_prefixed(mut bytes: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    let mut records = Vec::new();
+    while !bytes.is_empty() {
+        if bytes.len() < LENGTH_PREFIX_SIZE {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Record {} contains no length prefix", records.len())));
+        }
+        let (length, rest) = bytes.split_at(LENGTH_PREFIX_SIZE);
+        let length = u32::from_be_bytes([length[0], length[1], length[2], length[3]]) as usize;
+        if rest.len() < length {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Record {} is truncated", records.len())));
+        }
+        let (record, rest) = rest.split_at(length);
+        records.push(record);
+        bytes = rest;
+    }
+    Ok(records)
+}
+
+pub struct LogWriter<'a, W: Write + Seek> {
+    format: &'a dyn Format,
+    writer: W
+}
+
+impl<'a, W: Write + Seek> LogWriter<'a, W> {
+    pub fn new(format: &'a dyn Format, writer: W) -> Self {
+        LogWriter {
+            format,
+            writer
+        }
+    }
+
+    pub fn append(&mut self, payload: &TransactionPayload) -> Result<u64, Error> {
+        let serialized_payload = self.format.serialize(payload)?;
+        let length = length_prefix(&serialized_payload)?;
+
+        let offset = self.writer.seek(SeekFrom::End(0))
+            .map_err(|error| Error::InvalidPayload(ErrorReason::Io, error_text!("Could not seek the log: {}", error)))?;
+        self.writer.write_all(&length)
+            .and_then(|_| self.writer.write_all(&serialized_payload))
+            .map_err(|error| Error::InvalidPayload(ErrorReason::Io, error_text!("Could not write the log: {}", error)))?;
+        Ok(offset)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+pub struct LogReader<'a, R: Read + Seek> {
+    format: &'a dyn Format,
+    reader: R
+}
+
+impl<'a, R: Read + Seek> LogReader<'a, R> {
+    pub fn new(format: &'a dyn Format, reader: R) -> Self {
+        LogReader {
+            format,
+            reader
+        }
+    }
+
+    pub fn read_at(&mut self, offset: u64) -> ParsingResult {
+        self.reader.seek(SeekFrom::Start(offset))
+            .map_err(|error| Error::InvalidPayload(ErrorReason::Io, error_text!("Could not seek the log: {}", error)))?;
+
+        let mut length = [0; LENGTH_PREFIX_SIZE];
+        self.reader.read_exact(&mut length)
+            .map_err(|_| Error::InvalidPayload(ErrorReason::OutOfRange, error_text!("Log contains no record at offset {}", offset)))?;
+        // Read no more than the log holds so that a corrupt length cannot force a large allocation
+        let length = u64::from(u32::from_be_bytes(length));
+        let mut serialized_payload = Vec::new();
+        self.reader.by_ref().take(length).read_to_end(&mut serialized_payload)
+            .map_err(|error| Error::InvalidPayload(ErrorReason::Io, error_text!("Could not read the log: {}", error)))?;
+        if (serialized_payload.len() as u64) < length {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Log record at offset {} is truncated", offset)));
+        }
+
+        self.format.deserialize(&serialized_payload)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use crate::payloads::log::{split_length_prefixed, LogReader, LogWriter};
+    use crate::payloads::{pipe_separated, TransactionPayload};
+    #[cfg(not(feature = "compact-errors"))]
+    use crate::payloads::Error;
+
+    #[test]
+    fn records_can_be_read_by_their_offset() {
+        let format = pipe_separated::Format::default();
+        let payloads = [
+            TransactionPayload::new("id", "type", "first".as_bytes(), 1),
+            TransactionPayload::new("id", "type", "second".as_bytes(), 2),
+            TransactionPayload::new("id", "type", "third".as_bytes(), 3),
+        ];
+        let mut writer = LogWriter::new(&format, Cursor::new(Vec::new()));
+
+        let offsets: Vec<u64> = payloads.iter()
+            .map(|payload| writer.append(payload).expect("Could not append payload"))
+            .collect();
+        let mut reader = LogReader::new(&format, writer.into_inner());
+
+        assert_eq!(offsets, vec![0, 19, 39]);
+        assert_eq!(reader.read_at(offsets[1]).expect("Could not read payload"), payloads[1]);
+    }
+
+    #[test]
+    fn appending_continues_an_existing_log() {
+        let format = pipe_separated::Format::default();
+        let mut writer = LogWriter::new(&format, Cursor::new(Vec::new()));
+        writer.append(&TransactionPayload::new("id", "type", "msg".as_bytes(), 1)).expect("Could not append payload");
+        let mut writer = LogWriter::new(&format, writer.into_inner());
+
+        let offset = writer.append(&TransactionPayload::new("id", "type", "msg".as_bytes(), 2))
+            .expect("Could not append payload");
+
+        assert_eq!(offset, 17)
+    }
+
+    #[test]
+    fn reading_past_the_end_of_the_log_fails() {
+        let format = pipe_separated::Format::default();
+        let mut writer = LogWriter::new(&format, Cursor::new(Vec::new()));
+        let offset = writer.append(&TransactionPayload::new("id", "type", "msg".as_bytes(), 1))
+            .expect("Could not append payload");
+        let mut reader = LogReader::new(&format, writer.into_inner());
+
+        assert!(reader.read_at(offset + 100).is_err())
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn a_truncated_record_is_reported() {
+        let format = pipe_separated::Format::default();
+        let mut writer = LogWriter::new(&format, Cursor::new(Vec::new()));
+        writer.append(&TransactionPayload::new("id", "type", "msg".as_bytes(), 1)).expect("Could not append payload");
+        let mut log = writer.into_inner().into_inner();
+        log.truncate(log.len() - 2);
+
+        let result = LogReader::new(&format, Cursor::new(log)).read_at(0);
+
+        match result {
+            Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "Log record at offset 0 is truncated"),
+            _ => panic!("Expected an invalid payload error")
+        }
+    }
+
+    #[test]
+    fn an_oversized_length_prefix_is_reported_as_truncated() {
+        let format = pipe_separated::Format::default();
+        let log = [&u32::MAX.to_be_bytes()[..], "id|type|msg|1".as_bytes()].concat();
+
+        let result = LogReader::new(&format, Cursor::new(log)).read_at(0);
+
+        assert!(result.is_err())
+    }
+
+    #[test]
+    fn length_prefixed_records_are_split_again() {
+        let bytes = [&2u32.to_be_bytes()[..], b"\n\n", &0u32.to_be_bytes(), &1u32.to_be_bytes(), b"a"].concat();
+
+        let records = split_length_prefixed(&bytes).expect("Could not split records");
+
+        assert_eq!(records, vec![&b"\n\n"[..], &b""[..], &b"a"[..]])
+    }
+
+    #[test]
+    fn a_truncated_length_prefixed_record_is_not_split() {
+        let bytes = [&5u32.to_be_bytes()[..], b"abc"].concat();
+
+        assert!(split_length_prefixed(&bytes).is_err());
+        assert!(split_length_prefixed(&bytes[..2]).is_err())
+    }
+}
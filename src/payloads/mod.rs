@@ -1,28 +1,182 @@
 pub mod pipe_separated;
+pub mod batch;
+pub mod protobuf;
+pub mod tagged;
+pub mod json;
+pub mod checked;
+pub mod log;
 
+use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter, Result};
+use std::io::BufRead;
 use mockall;
 
+use crate::helper;
+use crate::error::{CrateError, ErrorReason};
+use crate::messages::registry::ValidatorRegistry;
+use crate::messages::MessageType;
+use crate::helper::{error_text, HashAlgorithm, NormalizationOptions};
+
 pub type ParsingResult = std::result::Result<TransactionPayload, Error>;
 pub type SerializationResult = std::result::Result<Vec<u8>, Error>;
 
+pub const MESSAGE_ENCODING: data_encoding::Encoding = data_encoding::BASE64;
+
+pub fn encode_message(message_bytes: &[u8]) -> String {
+    MESSAGE_ENCODING.encode(message_bytes)
+}
+
+pub fn decode_message(encoded_message: &str) -> std::result::Result<Vec<u8>, Error> {
+    MESSAGE_ENCODING.decode(encoded_message.as_bytes())
+        .map_err(|_| Error::InvalidPayload(ErrorReason::NotBase64, error_text!("Message is not encoded in the standard base64 alphabet")))
+}
+
+pub fn validate_family_name(family_name: &str) -> std::result::Result<(), Error> {
+    let is_valid_character = |character: char| character.is_ascii_alphanumeric() || "_-.".contains(character);
+    if family_name.is_empty() || !family_name.chars().all(is_valid_character) {
+        return Err(Error::InvalidPayload(
+            ErrorReason::DisallowedValue, error_text!("Payload has invalid family name {}", helper::truncate_for_error(family_name))));
+    }
+    Ok(())
+}
+
 #[mockall::automock]
 pub trait Format {
     fn serialize(&self, payload: &TransactionPayload) -> SerializationResult;
 
     fn deserialize(&self, bytes: &[u8]) -> ParsingResult;
+
+    // Records are separated by line breaks, binary formats override this with length prefixed records
+    fn serialize_batch(&self, payloads: &[TransactionPayload]) -> SerializationResult {
+        let mut output = Vec::with_capacity(payloads.iter().map(TransactionPayload::estimated_serialized_len).sum());
+        for (index, payload) in payloads.iter().enumerate() {
+            if index > 0 {
+                output.push(b'\n');
+            }
+            let record = self.serialize(payload)?;
+            if record.contains(&b'\n') {
+                return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload {} contains a record separator", index)));
+            }
+            output.extend_from_slice(&record);
+        }
+        Ok(output)
+    }
+}
+
+pub trait Parser {
+    fn parse(&self, bytes: &[u8]) -> ParsingResult;
+}
+
+pub trait Serializer {
+    fn serialize(&self, payload: &TransactionPayload) -> SerializationResult;
+}
+
+impl<F: Format + ?Sized> Parser for F {
+    fn parse(&self, bytes: &[u8]) -> ParsingResult {
+        self.deserialize(bytes)
+    }
+}
+
+impl<F: Format + ?Sized> Serializer for F {
+    fn serialize(&self, payload: &TransactionPayload) -> SerializationResult {
+        Format::serialize(self, payload)
+    }
+}
+
+pub fn transcode(bytes: &[u8], from: &dyn Format, to: &dyn Format) -> SerializationResult {
+    let payload = from.deserialize(bytes)?;
+    to.serialize(&payload)
+}
+
+pub fn formats_agree(bytes: &[u8], a: &dyn Format, b: &dyn Format) -> std::result::Result<bool, Error> {
+    Ok(a.deserialize(bytes)? == b.deserialize(bytes)?)
+}
+
+pub struct MultiFormatReader {
+    formats: Vec<Box<dyn Format>>
+}
+
+impl MultiFormatReader {
+    pub fn new(formats: Vec<Box<dyn Format>>) -> Self {
+        MultiFormatReader {
+            formats
+        }
+    }
+
+    pub fn parse(&self, bytes: &[u8]) -> ParsingResult {
+        let mut errors = Vec::with_capacity(self.formats.len());
+        for format in &self.formats {
+            match format.deserialize(bytes) {
+                Ok(payload) => return Ok(payload),
+                Err(error) => errors.push(error)
+            }
+        }
+
+        let tried_formats: Vec<String> = errors.iter().enumerate()
+            .map(|(index, error)| error_text!("format {}: {}", index, error))
+            .collect();
+        Err(Error::InvalidPayload(ErrorReason::Unsupported, error_text!("No format could parse the record ({})", tried_formats.join("; "))))
+    }
+
+    pub fn records<'a, R: BufRead + 'a>(&'a self, reader: R) -> impl Iterator<Item = ParsingResult> + 'a {
+        reader.split(b'\n').map(move |line| {
+            let line = line.map_err(|_| Error::InvalidPayload(ErrorReason::Io, error_text!("Record could not be read")))?;
+            self.parse(line.strip_suffix(b"\r").unwrap_or(&line))
+        })
+    }
+}
+
+pub fn group_by_message_type(payloads: Vec<TransactionPayload>) -> BTreeMap<String, Vec<TransactionPayload>> {
+    let mut groups: BTreeMap<String, Vec<TransactionPayload>> = BTreeMap::new();
+    for payload in payloads {
+        groups.entry(payload.message_type.clone()).or_default().push(payload);
+    }
+    groups
+}
+
+pub fn validate_monotonic(payloads: &[TransactionPayload]) -> std::result::Result<(), Error> {
+    match payloads.windows(2).position(|pair| pair[1].timestamp < pair[0].timestamp) {
+        Some(index) => Err(Error::InvalidPayload(
+            ErrorReason::InvalidTimestamp, error_text!("Timestamp at index {} is earlier than its predecessor", index + 1))),
+        None => Ok(())
+    }
+}
+
+pub fn validate_freshness(payload: &TransactionPayload, now: u64, max_skew: u64) -> std::result::Result<(), Error> {
+    if payload.timestamp.abs_diff(now) > max_skew {
+        Err(Error::InvalidTimestamp)
+    } else {
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
 pub enum Error {
-    InvalidPayload(String),
+    InvalidPayload(ErrorReason, String),
     InvalidTimestamp,
 }
 
+impl Error {
+    pub fn reason(&self) -> ErrorReason {
+        match self {
+            Error::InvalidPayload(reason, _) => *reason,
+            Error::InvalidTimestamp => ErrorReason::InvalidTimestamp,
+        }
+    }
+}
+
+#[cfg(feature = "compact-errors")]
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
+        write!(formatter, "P{}", self.reason().code())
+    }
+}
+
+#[cfg(not(feature = "compact-errors"))]
 impl Display for Error {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
         let message = match self {
-            Error::InvalidPayload(message) => message,
+            Error::InvalidPayload(_, message) => message,
             Error::InvalidTimestamp => "Payload contains invalid timestamp",
         };
 
@@ -30,12 +184,18 @@ impl Display for Error {
     }
 }
 
+impl std::error::Error for Error {}
+
+/// The fields stay public `String`s: callers construct and assign them directly, so storing them as small
+/// strings behind a cargo feature would change their type whenever any crate in the build enables it.
+/// Prefer the `&str` accessors, which keep working once the fields become private in a breaking release.
 #[derive(PartialEq, Debug)]
 pub struct TransactionPayload {
     pub agent_id: String,
     pub message_type: String,
     pub message_bytes: Vec<u8>,
     pub timestamp: u64,
+    pub family_name: Option<String>,
 }
 
 impl TransactionPayload {
@@ -45,8 +205,82 @@ impl TransactionPayload {
             message_type: message_type.to_string(),
             message_bytes: message_bytes.to_vec(),
             timestamp,
+            family_name: None,
         }
     }
+
+    pub fn estimated_serialized_len(&self) -> usize {
+        // Field contents plus room for delimiters, a decimal timestamp and the record separator
+        self.agent_id.len() + self.message_type.len() + self.message_bytes.len()
+            + self.family_name.as_ref().map_or(0, |family_name| family_name.len() + 1) + 24
+    }
+
+    pub fn agent_id(&self) -> &str {
+        &self.agent_id
+    }
+
+    pub fn message_type(&self) -> &str {
+        &self.message_type
+    }
+
+    pub fn is_known_message_type(&self) -> bool {
+        MessageType::from_name(&self.message_type).is_some()
+    }
+
+    pub fn with_family_name(mut self, family_name: &str) -> Self {
+        self.family_name = Some(family_name.to_string());
+        self
+    }
+
+    pub fn from_validated_json(message_type: &str, message: &[u8], agent_id: &str, timestamp: u64,
+                               registry: &ValidatorRegistry) -> std::result::Result<Self, CrateError> {
+        registry.validate(message_type, message)?;
+        Ok(TransactionPayload::new(agent_id, message_type, message, timestamp))
+    }
+
+    pub fn same_content(&self, other: &TransactionPayload) -> bool {
+        self.agent_id == other.agent_id
+            && self.message_type == other.message_type
+            && self.message_bytes == other.message_bytes
+    }
+
+    pub fn content_id(&self) -> String {
+        self.content_id_with(&NormalizationOptions::default())
+    }
+
+    pub fn content_id_with(&self, normalization: &NormalizationOptions) -> String {
+        let agent_id = normalization.normalize_field(&self.agent_id);
+        let message_type = normalization.normalize_field(&self.message_type);
+        let message_bytes = normalization.normalize_message(&self.message_bytes);
+
+        let mut content = length_prefixed(&[agent_id.as_bytes(), message_type.as_bytes(), &message_bytes]);
+        content.extend_from_slice(&normalization.normalize_timestamp(self.timestamp).to_be_bytes());
+        // Appended only when present so that content ids of payloads without a family stay unchanged
+        if let Some(family_name) = &self.family_name {
+            content.extend_from_slice(&length_prefixed(&[family_name.as_bytes()]));
+        }
+        helper::calculate_checksum(&content)
+    }
+
+    /// Derived for idempotent resubmission only, the nonce is predictable and must not be used for security
+    pub fn deterministic_nonce(&self) -> String {
+        let family_name = self.family_name.as_deref().unwrap_or_default();
+        let mut content = length_prefixed(&[NONCE_SALT, self.agent_id.as_bytes(), self.message_type.as_bytes(),
+                                            &self.message_bytes, family_name.as_bytes()]);
+        content.extend_from_slice(&self.timestamp.to_be_bytes());
+        helper::calculate_checksum_with(HashAlgorithm::Sha256, &content)
+    }
+}
+
+const NONCE_SALT: &[u8] = b"sawtooth-alica-payload-nonce";
+
+fn length_prefixed(fields: &[&[u8]]) -> Vec<u8> {
+    let mut content = Vec::new();
+    for field in fields {
+        content.extend_from_slice(&(field.len() as u64).to_be_bytes());
+        content.extend_from_slice(field);
+    }
+    content
 }
 
 impl Default for TransactionPayload {
@@ -55,7 +289,514 @@ impl Default for TransactionPayload {
             agent_id: "".to_string(),
             message_type: "".to_string(),
             message_bytes: "".as_bytes().to_vec(),
-            timestamp: 0
+            timestamp: 0,
+            family_name: None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::payloads::{decode_message, encode_message, group_by_message_type, validate_freshness, validate_monotonic,
+                          Error, TransactionPayload, Parser, Serializer, pipe_separated};
+
+    #[test]
+    fn encoded_messages_use_the_standard_base64_alphabet() {
+        let message = vec![0xfb, 0xff];
+
+        let encoded_message = encode_message(&message);
+
+        assert_eq!(encoded_message, "+/8=")
+    }
+
+    #[test]
+    fn encoded_messages_can_be_decoded() {
+        let message = vec![0xfb, 0xff, 0x00, 0x7c];
+
+        let decoded_message = decode_message(&encode_message(&message)).expect("Could not decode message");
+
+        assert_eq!(decoded_message, message)
+    }
+
+    #[test]
+    fn messages_encoded_in_the_url_safe_alphabet_are_rejected() {
+        let result = decode_message("-_8=");
+
+        match result {
+            Err(Error::InvalidPayload(_, _)) => (),
+            _ => panic!("Expected an invalid payload error")
+        }
+    }
+
+    #[test]
+    fn payloads_within_the_allowed_skew_are_fresh() {
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 995);
+
+        let result = validate_freshness(&payload, 1000, 10);
+
+        assert!(result.is_ok())
+    }
+
+    #[test]
+    fn payloads_older_than_the_allowed_skew_are_rejected() {
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 989);
+
+        let result = validate_freshness(&payload, 1000, 10);
+
+        match result {
+            Err(Error::InvalidTimestamp) => (),
+            _ => panic!("Expected an invalid timestamp error")
+        }
+    }
+
+    #[test]
+    fn payloads_newer_than_the_allowed_skew_are_rejected() {
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1011);
+
+        let result = validate_freshness(&payload, 1000, 10);
+
+        match result {
+            Err(Error::InvalidTimestamp) => (),
+            _ => panic!("Expected an invalid timestamp error")
+        }
+    }
+
+    #[test]
+    fn payloads_are_grouped_by_message_type() {
+        let payloads = vec![
+            TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "SyncReady", "msg".as_bytes(), 2),
+            TransactionPayload::new("other id", "RoleSwitch", "msg".as_bytes(), 3),
+        ];
+
+        let groups = group_by_message_type(payloads);
+
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["RoleSwitch", "SyncReady"]);
+        assert_eq!(groups["RoleSwitch"].len(), 2);
+        assert_eq!(groups["SyncReady"].len(), 1);
+        assert_eq!(groups["RoleSwitch"][1].timestamp, 3);
+    }
+
+    #[test]
+    fn non_decreasing_timestamps_are_monotonic() {
+        let payloads = [
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 3),
+        ];
+
+        let result = validate_monotonic(&payloads);
+
+        assert!(result.is_ok())
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn a_backward_step_is_reported_at_its_index() {
+        let payloads = [
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 1),
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 5),
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 4),
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 2),
+        ];
+
+        let result = validate_monotonic(&payloads);
+
+        match result {
+            Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "Timestamp at index 2 is earlier than its predecessor"),
+            _ => panic!("Expected an invalid payload error")
+        }
+    }
+
+    #[test]
+    fn built_in_message_types_are_known() {
+        assert!(TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1).is_known_message_type());
+        assert!(!TransactionPayload::new("id", "Custom", "msg".as_bytes(), 1).is_known_message_type());
+    }
+
+    #[test]
+    fn short_fields_are_accessible_as_str() {
+        let payload = TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1);
+
+        assert_eq!(payload.agent_id(), "id");
+        assert_eq!(payload.message_type(), "RoleSwitch");
+    }
+
+    #[test]
+    fn formats_can_be_used_as_parsers() {
+        let format = pipe_separated::Format::default();
+        let parser: &dyn Parser = &format;
+
+        let payload = parser.parse("id|type|msg|1".as_bytes()).expect("Could not parse payload");
+
+        assert_eq!(payload, TransactionPayload::new("id", "type", "msg".as_bytes(), 1))
+    }
+
+    #[test]
+    fn formats_can_be_used_as_serializers() {
+        let format = pipe_separated::Format::default();
+        let serializer: &dyn Serializer = &format;
+        let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+        let serialized_payload = serializer.serialize(&payload).expect("Could not serialize payload");
+
+        assert_eq!(serialized_payload, "id|type|msg|1".as_bytes().to_vec())
+    }
+
+    mod same_content {
+        use crate::payloads::TransactionPayload;
+
+        #[test]
+        fn payloads_differing_only_in_timestamp_have_the_same_content() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 2);
+
+            assert!(payload.same_content(&other_payload));
+            assert_ne!(payload, other_payload);
+        }
+
+        #[test]
+        fn payloads_with_different_messages_do_not_have_the_same_content() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "other msg".as_bytes(), 1);
+
+            assert!(!payload.same_content(&other_payload))
+        }
+    }
+
+    mod content_id {
+        use crate::helper::NormalizationOptions;
+        use crate::payloads::TransactionPayload;
+
+        #[test]
+        fn equal_payloads_have_the_same_content_id() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            assert_eq!(payload.content_id(), other_payload.content_id())
+        }
+
+        #[test]
+        fn the_content_id_depends_on_the_agent_id() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("other id", "type", "msg".as_bytes(), 1);
+
+            assert_ne!(payload.content_id(), other_payload.content_id())
+        }
+
+        #[test]
+        fn the_content_id_depends_on_the_message_type() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "other type", "msg".as_bytes(), 1);
+
+            assert_ne!(payload.content_id(), other_payload.content_id())
+        }
+
+        #[test]
+        fn the_content_id_depends_on_the_message() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "other msg".as_bytes(), 1);
+
+            assert_ne!(payload.content_id(), other_payload.content_id())
+        }
+
+        #[test]
+        fn the_content_id_depends_on_the_timestamp() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 2);
+
+            assert_ne!(payload.content_id(), other_payload.content_id())
+        }
+
+        #[test]
+        fn the_content_id_depends_on_the_family_name() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1).with_family_name("alica");
+            let other_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1).with_family_name("other");
+
+            assert_ne!(payload.content_id(), other_payload.content_id());
+            assert_ne!(payload.content_id(), TransactionPayload::new("id", "type", "msg".as_bytes(), 1).content_id());
+        }
+
+        #[test]
+        fn the_content_id_distinguishes_field_boundaries() {
+            let payload = TransactionPayload::new("ab", "c", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("a", "bc", "msg".as_bytes(), 1);
+
+            assert_ne!(payload.content_id(), other_payload.content_id())
+        }
+
+        #[test]
+        fn whitespace_varied_json_messages_have_the_same_normalized_content_id() {
+            let normalization = NormalizationOptions { canonical_json: true, ..NormalizationOptions::default() };
+            let payload = TransactionPayload::new("id", "type", "{\"roleId\": 1}\n".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "{\"roleId\":1}".as_bytes(), 1);
+
+            assert_eq!(payload.content_id_with(&normalization), other_payload.content_id_with(&normalization));
+            assert_ne!(payload.content_id(), other_payload.content_id());
+        }
+
+        #[test]
+        fn json_messages_differing_in_key_order_have_the_same_normalized_content_id() {
+            let normalization = NormalizationOptions { canonical_json: true, ..NormalizationOptions::default() };
+            let payload = TransactionPayload::new("id", "type", "{\"roleId\":1,\"ack\":true}".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "{\"ack\":true,\"roleId\":1}".as_bytes(), 1);
+
+            assert_eq!(payload.content_id_with(&normalization), other_payload.content_id_with(&normalization))
+        }
+
+        #[test]
+        fn non_json_messages_are_not_normalized() {
+            let normalization = NormalizationOptions { canonical_json: true, ..NormalizationOptions::default() };
+            let payload = TransactionPayload::new("id", "type", "msg \n".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            assert_ne!(payload.content_id_with(&normalization), other_payload.content_id_with(&normalization))
+        }
+    }
+
+    mod deterministic_nonce {
+        use crate::payloads::TransactionPayload;
+
+        #[test]
+        fn equal_payloads_share_a_nonce() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let other_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            assert_eq!(payload.deterministic_nonce(), other_payload.deterministic_nonce())
+        }
+
+        #[test]
+        fn differing_payloads_have_different_nonces() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            assert_ne!(payload.deterministic_nonce(), TransactionPayload::new("id", "type", "msg".as_bytes(), 2).deterministic_nonce());
+            assert_ne!(payload.deterministic_nonce(), TransactionPayload::new("id", "type", "other".as_bytes(), 1).deterministic_nonce());
+            assert_ne!(payload.deterministic_nonce(),
+                       TransactionPayload::new("id", "type", "msg".as_bytes(), 1).with_family_name("alica").deterministic_nonce());
+        }
+
+        #[test]
+        fn the_nonce_differs_from_the_content_id() {
+            let payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            assert_ne!(payload.deterministic_nonce(), payload.content_id())
+        }
+    }
+
+    mod from_validated_json {
+        use crate::error::CrateError;
+        use crate::messages::json::RoleSwitchValidator;
+        use crate::messages::registry::ValidatorRegistry;
+        use crate::payloads::TransactionPayload;
+
+        fn registry() -> ValidatorRegistry {
+            let mut registry = ValidatorRegistry::new();
+            registry.register("RoleSwitch", Box::new(RoleSwitchValidator::new()));
+            registry
+        }
+
+        #[test]
+        fn payloads_are_built_around_valid_messages() {
+            let role_switch = json::object!{
+                senderId: {
+                    type: 0,
+                    value: "id"
+                },
+                roleId: 1
+            }.dump();
+
+            let payload = TransactionPayload::from_validated_json("RoleSwitch", role_switch.as_bytes(), "id", 1, &registry())
+                .expect("Could not build payload");
+
+            assert_eq!(payload, TransactionPayload::new("id", "RoleSwitch", role_switch.as_bytes(), 1))
+        }
+
+        #[test]
+        fn payloads_are_not_built_around_invalid_messages() {
+            let role_switch = json::object!{}.dump();
+
+            let result = TransactionPayload::from_validated_json("RoleSwitch", role_switch.as_bytes(), "id", 1, &registry());
+
+            match result {
+                Err(CrateError::Validation(_)) => (),
+                _ => panic!("Expected a validation error")
+            }
+        }
+    }
+
+    #[cfg(feature = "compact-errors")]
+    mod compact_errors {
+        use crate::error::ErrorReason;
+        use crate::payloads::{decode_message, Error};
+
+        #[test]
+        fn payload_errors_are_distinguished_by_their_reason() {
+            let not_base64_error = decode_message("not base64!").expect_err("Expected an invalid payload error");
+
+            assert_eq!(not_base64_error.reason(), ErrorReason::NotBase64);
+            assert_eq!(not_base64_error.to_string(), "P4");
+            assert_eq!(Error::InvalidTimestamp.to_string(), "P11");
+        }
+    }
+
+    mod batch_serialization {
+        use crate::payloads::{json, pipe_separated, Format, TransactionPayload};
+
+        #[test]
+        fn a_serialized_batch_splits_back_into_its_payloads() {
+            let format = pipe_separated::Format::default();
+            let payloads = vec![
+                TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+                TransactionPayload::new("other id", "SyncReady", "other msg".as_bytes(), 2),
+                TransactionPayload::new("id", "Bid", "{}".as_bytes(), 3),
+            ];
+
+            let batch = format.serialize_batch(&payloads).expect("Could not serialize batch");
+            let result: Vec<TransactionPayload> = batch.split(|byte| *byte == b'\n')
+                .map(|record| format.deserialize(record).expect("Could not parse record"))
+                .collect();
+
+            assert_eq!(result, payloads)
+        }
+
+        #[test]
+        fn an_empty_batch_serializes_to_no_bytes() {
+            let batch = pipe_separated::Format::default().serialize_batch(&[]).expect("Could not serialize batch");
+
+            assert!(batch.is_empty())
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_payload_containing_a_line_break_is_not_batched() {
+            let payloads = vec![
+                TransactionPayload::new("id", "RoleSwitch", "msg".as_bytes(), 1),
+                TransactionPayload::new("other\nid", "SyncReady", "other msg".as_bytes(), 2),
+            ];
+
+            let result = pipe_separated::Format::default().serialize_batch(&payloads);
+
+            assert_eq!(result.expect_err("Batched a line break").to_string(), "Payload 1 contains a record separator")
+        }
+
+        #[test]
+        fn json_payloads_escape_line_breaks_in_batches() {
+            let payload = TransactionPayload::new("other\nid", "SyncReady", "other msg".as_bytes(), 2);
+
+            let batch = json::Format::default().serialize_batch(&[payload]);
+
+            assert!(batch.is_ok())
+        }
+    }
+
+    mod multi_format_reader {
+        use crate::payloads::{json, pipe_separated, Format, MultiFormatReader, TransactionPayload};
+
+        fn reader() -> MultiFormatReader {
+            MultiFormatReader::new(vec![
+                Box::new(pipe_separated::Format::default()),
+                Box::new(json::Format::default()),
+            ])
+        }
+
+        #[test]
+        fn each_record_is_parsed_by_the_matching_format() {
+            let pipe_separated_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let json_payload = TransactionPayload::new("other id", "other type", "other msg".as_bytes(), 2);
+            let mut archive = pipe_separated::Format::default().serialize(&pipe_separated_payload)
+                .expect("Could not serialize payload");
+            archive.push(b'\n');
+            archive.extend(json::Format::default().serialize(&json_payload).expect("Could not serialize payload"));
+            archive.push(b'\n');
+
+            let reader = reader();
+            let results: Vec<TransactionPayload> = reader.records(archive.as_slice())
+                .collect::<Result<_, _>>()
+                .expect("Could not parse records");
+
+            assert_eq!(results, vec![pipe_separated_payload, json_payload])
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn records_no_format_can_parse_report_the_tried_formats() {
+            let reader = reader();
+
+            let results: Vec<_> = reader.records("garbage\n".as_bytes()).collect();
+
+            match &results[..] {
+                [Err(error)] => {
+                    let message = error.to_string();
+                    assert!(message.starts_with("No format could parse the record"));
+                    assert!(message.contains("format 0: "));
+                    assert!(message.contains("format 1: "));
+                },
+                _ => panic!("Expected a single invalid payload error")
+            }
+        }
+    }
+
+    mod transcoding {
+        use crate::payloads::{formats_agree, json, pipe_separated, transcode, Format, TransactionPayload};
+
+        #[test]
+        fn payloads_can_be_transcoded_to_json_and_back() {
+            let pipe_separated_format = pipe_separated::Format::default();
+            let json_format = json::Format::default();
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+            let pipe_separated_payload = pipe_separated_format.serialize(&transaction_payload)
+                .expect("Could not serialize payload");
+
+            let json_payload = transcode(&pipe_separated_payload, &pipe_separated_format, &json_format)
+                .expect("Could not transcode payload");
+            let result = transcode(&json_payload, &json_format, &pipe_separated_format)
+                .expect("Could not transcode payload");
+
+            assert_eq!(json_format.deserialize(&json_payload).expect("Could not parse payload"), transaction_payload);
+            assert_eq!(result, pipe_separated_payload)
+        }
+
+        #[test]
+        fn family_names_are_kept_when_transcoding() {
+            let pipe_separated_format = pipe_separated::Format::default().with_family_name_segment();
+            let json_format = json::Format::default();
+            let pipe_separated_payload = "id|type|msg|1|alica".as_bytes();
+
+            let json_payload = transcode(pipe_separated_payload, &pipe_separated_format, &json_format)
+                .expect("Could not transcode payload");
+
+            assert_eq!(json_format.deserialize(&json_payload).expect("Could not parse payload").family_name,
+                       Some("alica".to_string()));
+        }
+
+        #[test]
+        fn formats_reading_a_payload_identically_agree() {
+            let result = formats_agree("id|type|msg|1".as_bytes(), &pipe_separated::Format::default(),
+                                       &pipe_separated::Format::default().with_field_trim(true));
+
+            assert!(result.expect("Could not parse payload"))
+        }
+
+        #[test]
+        fn formats_reading_a_payload_differently_disagree() {
+            let result = formats_agree("id|type|msg\0|1".as_bytes(), &pipe_separated::Format::default(),
+                                       &pipe_separated::Format::default().with_trailing_nul_strip(true));
+
+            assert!(!result.expect("Could not parse payload"))
+        }
+
+        #[test]
+        fn formats_cannot_agree_on_unparsable_payloads() {
+            let result = formats_agree("id|type".as_bytes(), &pipe_separated::Format::default(), &json::Format::default());
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn invalid_payloads_are_not_transcoded() {
+            let result = transcode("id|type".as_bytes(), &pipe_separated::Format::default(), &json::Format::default());
+
+            assert!(result.is_err())
         }
     }
 }
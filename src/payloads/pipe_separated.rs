@@ -1,51 +1,338 @@
 use crate::payloads::{Error, ParsingResult, TransactionPayload, SerializationResult};
 use crate::payloads;
+use crate::helper::error_text;
+use crate::error::ErrorReason;
+use crate::messages::MessageType;
 
-pub struct Format {}
+pub const DEFAULT_DELIMITER: &str = "|";
+pub const DEFAULT_FIELD_COUNT: usize = 4;
+
+pub struct Format {
+    delimiter: String,
+    trim_fields: bool,
+    strip_trailing_nul: bool,
+    append_trailing_nul: bool,
+    length_prefixed_message: bool,
+    strict_delimiters: bool,
+    reject_leading_zeros: bool,
+    known_message_types_only: bool,
+    reject_delimiter_in_fields: bool,
+    family_name_segment: bool,
+    expected_field_count: Option<usize>
+}
 
 impl Format {
     pub fn new() -> Self {
-        Format {}
+        Format {
+            delimiter: DEFAULT_DELIMITER.to_string(),
+            trim_fields: false,
+            strip_trailing_nul: false,
+            append_trailing_nul: false,
+            length_prefixed_message: false,
+            strict_delimiters: false,
+            reject_leading_zeros: false,
+            known_message_types_only: false,
+            reject_delimiter_in_fields: false,
+            family_name_segment: false,
+            expected_field_count: None
+        }
+    }
+
+    pub fn with_delimiter(self, delimiter: char) -> Result<Self, Error> {
+        self.with_delimiter_str(delimiter.encode_utf8(&mut [0; 4]))
+    }
+
+    pub fn with_delimiter_str(mut self, delimiter: &str) -> Result<Self, Error> {
+        if delimiter.is_empty() {
+            return Err(Error::InvalidPayload(ErrorReason::DisallowedValue, error_text!("Delimiter must not be empty")));
+        }
+        if delimiter.chars().all(is_base64_char) {
+            return Err(Error::InvalidPayload(ErrorReason::DisallowedValue, error_text!("Delimiter can occur in base64 encoded content")));
+        }
+        self.delimiter = delimiter.to_string();
+        Ok(self)
+    }
+
+    pub fn with_field_trim(mut self, trim_fields: bool) -> Self {
+        self.trim_fields = trim_fields;
+        self
+    }
+
+    pub fn with_trailing_nul_strip(mut self, strip_trailing_nul: bool) -> Self {
+        self.strip_trailing_nul = strip_trailing_nul;
+        self
+    }
+
+    pub fn with_trailing_nul_append(mut self, append_trailing_nul: bool) -> Self {
+        self.append_trailing_nul = append_trailing_nul;
+        self
+    }
+
+    pub fn with_length_prefixed_message(mut self) -> Self {
+        self.length_prefixed_message = true;
+        self
+    }
+
+    /// By default a leading delimiter shifts all fields and a trailing one fails the field count check
+    pub fn with_strict_delimiters(mut self) -> Self {
+        self.strict_delimiters = true;
+        self
+    }
+
+    /// Timestamps are always plain digits without sign, leading zeros as in "007" are accepted by default
+    pub fn with_leading_zeros_rejected(mut self) -> Self {
+        self.reject_leading_zeros = true;
+        self
+    }
+
+    pub fn with_known_message_types_only(mut self) -> Self {
+        self.known_message_types_only = true;
+        self
+    }
+
+    /// By default fields are written verbatim, even if a contained delimiter makes the payload unparseable
+    pub fn with_delimiter_in_fields_rejected(mut self) -> Self {
+        self.reject_delimiter_in_fields = true;
+        self
+    }
+
+    /// The family name segment is optional, payloads without it keep one field less
+    pub fn with_family_name_segment(mut self) -> Self {
+        self.family_name_segment = true;
+        self
     }
+
+    /// Segments beyond the timestamp and family name are counted but not read
+    pub fn with_expected_field_count(mut self, expected_field_count: usize) -> Self {
+        self.expected_field_count = Some(expected_field_count);
+        self
+    }
+
+    pub fn expected_field_count(&self) -> usize {
+        let default_field_count = if self.family_name_segment { DEFAULT_FIELD_COUNT + 1 } else { DEFAULT_FIELD_COUNT };
+        self.expected_field_count.unwrap_or(default_field_count)
+    }
+
+    pub fn peek_timestamp(&self, bytes: &[u8]) -> Result<u64, Error> {
+        // Whether the optional family name follows the timestamp is only known after parsing
+        if self.family_name_segment {
+            let mut fields = Fields::default();
+            self.parse_into(bytes, &mut fields)?;
+            return Ok(fields.timestamp.unwrap_or_default());
+        }
+        let delimiter = self.delimiter.as_bytes();
+        let mut content = bytes;
+        for _ in DEFAULT_FIELD_COUNT..self.expected_field_count() {
+            content = rsplit_once(content, delimiter).map(|(head, _)| head)
+                .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no timestamp")))?;
+        }
+        let timestamp = rsplit_once(content, delimiter).map(|(_, timestamp)| timestamp)
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no timestamp")))?;
+        let timestamp = std::str::from_utf8(timestamp).map_err(|_| Error::InvalidTimestamp)?;
+        self.parse_timestamp(self.trim_field(timestamp))
+    }
+
+    pub fn peek_message_type<'a>(&self, bytes: &'a [u8]) -> Result<&'a str, Error> {
+        let payload = std::str::from_utf8(bytes)
+            .map_err(|_| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload is not a string")))?;
+        if self.strict_delimiters && payload.starts_with(self.delimiter.as_str()) {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload starts with a delimiter")));
+        }
+
+        let message_type = payload.split(self.delimiter.as_str()).nth(1)
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no message type")))?;
+        Ok(self.trim_field(message_type))
+    }
+
+    pub fn deserialize_partial(&self, bytes: &[u8]) -> (PartialPayload, Option<Error>) {
+        let mut fields = Fields::default();
+        let error = self.parse_into(bytes, &mut fields).err();
+
+        let partial_payload = PartialPayload {
+            agent_id: fields.agent_id.map(|agent_id| agent_id.to_string()),
+            message_type: fields.message_type.map(|message_type| message_type.to_string()),
+            message_bytes: fields.message.map(|message| message.as_bytes().to_vec()),
+            timestamp: fields.timestamp,
+            family_name: fields.family_name.map(|family_name| family_name.to_string()),
+        };
+        (partial_payload, error)
+    }
+
+    pub fn deserialize_into(&self, bytes: &[u8], out: &mut TransactionPayload) -> Result<(), Error> {
+        let mut fields = Fields::default();
+        self.parse_into(bytes, &mut fields)?;
+
+        out.agent_id.clear();
+        out.agent_id.push_str(fields.agent_id.unwrap_or_default());
+        out.message_type.clear();
+        out.message_type.push_str(fields.message_type.unwrap_or_default());
+        out.message_bytes.clear();
+        out.message_bytes.extend_from_slice(fields.message.unwrap_or_default().as_bytes());
+        out.timestamp = fields.timestamp.unwrap_or_default();
+        out.family_name = fields.family_name.map(|family_name| family_name.to_string());
+        Ok(())
+    }
+
+    fn parse_into<'a>(&self, bytes: &'a [u8], fields: &mut Fields<'a>) -> Result<(), Error> {
+        let payload = std::str::from_utf8(bytes)
+            .map_err(|_| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload is not a string")))?;
+        if payload.trim().is_empty() {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("empty payload")));
+        }
+        if self.strict_delimiters && payload.starts_with(self.delimiter.as_str()) {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload starts with a delimiter")));
+        }
+        if self.strict_delimiters && payload.ends_with(self.delimiter.as_str()) {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload ends with a delimiter")));
+        }
+
+        let max_fields = if self.length_prefixed_message { 3 } else { usize::MAX };
+        let mut content = payload.splitn(max_fields, self.delimiter.as_str());
+        fields.agent_id = Some(self.trim_field(content.next()
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no agent id")))?));
+        let message_type = self.trim_field(content.next()
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no message type")))?);
+        if self.known_message_types_only && MessageType::from_name(message_type).is_none() {
+            return Err(Error::InvalidPayload(ErrorReason::UnknownMessageType,
+                error_text!("Payload has unknown message type {}", crate::helper::truncate_for_error(message_type))));
+        }
+        fields.message_type = Some(message_type);
+        let message = content.next()
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no message")))?;
+        let (message, remainder) = if self.length_prefixed_message {
+            self.split_length_prefixed_message(message)?
+        } else {
+            (self.trim_field(message), None)
+        };
+        let mut content = content.chain(remainder.into_iter().flat_map(|remainder| remainder.split(self.delimiter.as_str())));
+        let timestamp = content.next();
+        fields.message = Some(match message.strip_suffix('\0') {
+            Some(stripped_message) if self.strip_trailing_nul => stripped_message,
+            _ => message
+        });
+        fields.timestamp = Some(self.parse_timestamp(self.trim_field(timestamp
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no timestamp")))?))?);
+        let fifth_field = content.next();
+        let field_count = DEFAULT_FIELD_COUNT + fifth_field.iter().count() + content.count();
+        let family_name_omitted = self.family_name_segment && field_count + 1 == self.expected_field_count();
+        if field_count != self.expected_field_count() && !family_name_omitted {
+            return Err(Error::InvalidPayload(
+                ErrorReason::Malformed, error_text!("Payload has {} fields, expected {}", field_count, self.expected_field_count())));
+        }
+        let family_name = fifth_field.filter(|_| self.family_name_segment && !family_name_omitted);
+        if let Some(family_name) = family_name {
+            let family_name = self.trim_field(family_name);
+            payloads::validate_family_name(family_name)?;
+            fields.family_name = Some(family_name);
+        }
+
+        Ok(())
+    }
+
+    fn split_length_prefixed_message<'a>(&self, content: &'a str) -> Result<(&'a str, Option<&'a str>), Error> {
+        let (length, content) = content.split_once(':')
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Message has no length prefix")))?;
+        let length = length.parse::<usize>()
+            .map_err(|_| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Message length prefix is no number")))?;
+        let message = content.get(..length)
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::OutOfRange, error_text!("Message length exceeds the payload")))?;
+
+        Ok((message, content[length..].strip_prefix(self.delimiter.as_str())))
+    }
+
+    fn parse_timestamp(&self, timestamp: &str) -> Result<u64, Error> {
+        if !timestamp.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(Error::InvalidTimestamp);
+        }
+        if self.reject_leading_zeros && timestamp.len() > 1 && timestamp.starts_with('0') {
+            return Err(Error::InvalidTimestamp);
+        }
+        timestamp.parse::<u64>().map_err(|_| Error::InvalidTimestamp)
+    }
+
+    fn trim_field<'a>(&self, field: &'a str) -> &'a str {
+        if self.trim_fields { field.trim() } else { field }
+    }
+}
+
+fn rsplit_once<'a>(bytes: &'a [u8], delimiter: &[u8]) -> Option<(&'a [u8], &'a [u8])> {
+    let position = bytes.windows(delimiter.len()).rposition(|window| window == delimiter)?;
+    Some((&bytes[..position], &bytes[position + delimiter.len()..]))
+}
+
+fn is_base64_char(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '+' || character == '/' || character == '='
+}
+
+#[derive(Default)]
+struct Fields<'a> {
+    agent_id: Option<&'a str>,
+    message_type: Option<&'a str>,
+    message: Option<&'a str>,
+    timestamp: Option<u64>,
+    family_name: Option<&'a str>,
+}
+
+#[derive(PartialEq, Debug, Default)]
+pub struct PartialPayload {
+    pub agent_id: Option<String>,
+    pub message_type: Option<String>,
+    pub message_bytes: Option<Vec<u8>>,
+    pub timestamp: Option<u64>,
+    pub family_name: Option<String>,
 }
 
 impl Default for Format {
     fn default() -> Self {
-        Format {}
+        Format::new()
     }
 }
 
 impl payloads::Format for Format {
     fn serialize(&self, payload: &TransactionPayload) -> SerializationResult {
-        let message = String::from_utf8(payload.message_bytes.clone())
-            .map_err(|_| Error::InvalidPayload("Message is not a UTF8 String".to_string()))?;
-        let output = format!("{}|{}|{}|{}", payload.agent_id.clone(), payload.message_type.clone(), message, &payload.timestamp).as_bytes().to_vec();
+        let mut message = String::from_utf8(payload.message_bytes.clone())
+            .map_err(|error| match error.utf8_error().error_len() {
+                None => Error::InvalidPayload(
+                    ErrorReason::Malformed, error_text!("message body is truncated UTF-8 at byte {}", error.utf8_error().valid_up_to())),
+                Some(_) => Error::InvalidPayload(ErrorReason::Malformed, error_text!("Message is not a UTF8 String"))
+            })?;
+        if self.append_trailing_nul {
+            message.push('\0');
+        }
+        if self.length_prefixed_message {
+            message = format!("{}:{}", message.len(), message);
+        }
+        let delimited_fields = if self.length_prefixed_message { 2 } else { 3 };
+        if self.reject_delimiter_in_fields && [payload.agent_id.as_str(), payload.message_type.as_str(), message.as_str()][..delimited_fields].iter()
+            .any(|field| field.contains(self.delimiter.as_str())) {
+            return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload field contains the delimiter")));
+        }
+
+        let family_name = match (&payload.family_name, self.family_name_segment) {
+            (Some(family_name), true) => Some(family_name.clone()),
+            (None, _) => None,
+            (Some(_), false) => return Err(Error::InvalidPayload(ErrorReason::Conflict, error_text!("Format has no family name segment")))
+        };
+        if let Some(family_name) = &family_name {
+            payloads::validate_family_name(family_name)?;
+            if family_name.contains(self.delimiter.as_str()) {
+                return Err(Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload field contains the delimiter")));
+            }
+        }
+
+        let mut fields = vec![payload.agent_id.clone(), payload.message_type.clone(), message, payload.timestamp.to_string()];
+        fields.extend(family_name);
+        let output = fields
+            .join(&self.delimiter)
+            .as_bytes()
+            .to_vec();
         Ok(output)
     }
 
     fn deserialize(&self, bytes: &[u8]) -> ParsingResult {
-        let payload = String::from_utf8(bytes.to_vec())
-            .map_err(|_| Error::InvalidPayload("Payload is not a string".to_string()))?;
-
-        let mut content = payload.split("|");
-        let agent_id = content.next()
-            .ok_or_else(|| Error::InvalidPayload("Payload contains no agent id".to_string()))?;
-        let message_type = content.next()
-            .ok_or_else(|| Error::InvalidPayload("Payload contains no message type".to_string()))?;
-        let message_bytes = content.next()
-            .and_then(|message| Some(message.as_bytes()))
-            .ok_or_else(|| Error::InvalidPayload("Payload contains no message".to_string()))?;
-        let timestamp = content.next()
-            .ok_or_else(|| Error::InvalidPayload("Payload contains no timestamp".to_string()))?
-            .parse::<u64>()
-            .map_err(|_| Error::InvalidTimestamp)?;
-
-        Ok(TransactionPayload::new(
-            agent_id,
-            message_type,
-            message_bytes,
-            timestamp,
-        ))
+        let mut payload = TransactionPayload::default();
+        self.deserialize_into(bytes, &mut payload)?;
+        Ok(payload)
     }
 }
 
@@ -55,6 +342,8 @@ mod test {
 
     mod parsing {
         use crate::payloads::{pipe_separated, Format};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::payloads::Error;
 
         #[test]
         fn the_payload_is_valid_if_it_is_structured_properly() {
@@ -131,6 +420,31 @@ mod test {
             assert!(result.is_err());
         }
 
+        #[test]
+        fn fields_are_trimmed_if_enabled() {
+            let payload_bytes = " id | type | msg | 1 ".as_bytes();
+
+            let payload = pipe_separated::Format::default().with_field_trim(true).deserialize(payload_bytes)
+                .expect("Error parsing payload");
+
+            assert_eq!(payload.agent_id, "id");
+            assert_eq!(payload.message_type, "type");
+            assert_eq!(payload.message_bytes, "msg".as_bytes().to_vec());
+            assert_eq!(payload.timestamp, 1);
+        }
+
+        #[test]
+        fn fields_are_not_trimmed_by_default() {
+            let payload_bytes = " id | type | msg |1".as_bytes();
+
+            let payload = pipe_separated::Format::default().deserialize(payload_bytes)
+                .expect("Error parsing payload");
+
+            assert_eq!(payload.agent_id, " id ");
+            assert_eq!(payload.message_type, " type ");
+            assert_eq!(payload.message_bytes, " msg ".as_bytes().to_vec());
+        }
+
         #[test]
         fn empty_message_is_not_parsed() {
             let payload_bytes = "".as_bytes();
@@ -139,10 +453,670 @@ mod test {
 
             assert!(result.is_err());
         }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn empty_and_whitespace_only_messages_are_reported_as_empty() {
+            for payload_bytes in ["".as_bytes(), " \t\n".as_bytes()] {
+                let result = pipe_separated::Format::default().deserialize(payload_bytes);
+
+                match result {
+                    Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "empty payload"),
+                    _ => panic!("Expected an invalid payload error")
+                }
+            }
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_missing_timestamp_is_not_reported_as_empty_payload() {
+            let result = pipe_separated::Format::default().deserialize("id|type|msg".as_bytes());
+
+            match result {
+                Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "Payload contains no timestamp"),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
+    }
+
+    mod delimiters {
+        use crate::payloads::{pipe_separated, Format, TransactionPayload};
+
+        #[test]
+        fn payloads_survive_a_round_trip_with_a_multi_character_delimiter() {
+            let format = pipe_separated::Format::default().with_delimiter_str("::").expect("Could not set delimiter");
+            let transaction_payload = TransactionPayload::new("id", "type", "msg|with:colons".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+            assert_eq!(serialized_payload, "id::type::msg|with:colons::1".as_bytes().to_vec());
+            assert_eq!(result, transaction_payload)
+        }
+
+        #[test]
+        fn payloads_survive_a_round_trip_with_a_single_character_delimiter() {
+            let format = pipe_separated::Format::default().with_delimiter(';').expect("Could not set delimiter");
+            let transaction_payload = TransactionPayload::new("id", "type", "msg|with|pipes".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+            assert_eq!(result, transaction_payload)
+        }
+
+        #[test]
+        fn the_timestamp_can_be_peeked_with_a_multi_character_delimiter() {
+            let format = pipe_separated::Format::default().with_delimiter_str("::").expect("Could not set delimiter");
+
+            let timestamp = format.peek_timestamp("id::type::msg::42".as_bytes()).expect("Could not peek timestamp");
+
+            assert_eq!(timestamp, 42)
+        }
+
+        #[test]
+        fn an_empty_delimiter_is_rejected() {
+            let result = pipe_separated::Format::default().with_delimiter_str("");
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn delimiters_made_of_base64_characters_are_rejected() {
+            for delimiter in ["=", "ab", "+/"] {
+                let result = pipe_separated::Format::default().with_delimiter_str(delimiter);
+
+                assert!(result.is_err())
+            }
+        }
+
+        #[test]
+        fn single_character_delimiters_are_validated_as_well() {
+            for delimiter in ['a', '=', '/'] {
+                let result = pipe_separated::Format::default().with_delimiter(delimiter);
+
+                assert!(result.is_err())
+            }
+        }
+
+        #[test]
+        fn payloads_containing_the_delimiter_are_serialized_by_default() {
+            let transaction_payload = TransactionPayload::new("id", "type", "msg|with|pipes".as_bytes(), 1);
+
+            let result = pipe_separated::Format::default().serialize(&transaction_payload);
+
+            assert_eq!(result.expect("Could not serialize payload"), "id|type|msg|with|pipes|1".as_bytes().to_vec())
+        }
+
+        #[test]
+        fn payloads_containing_the_delimiter_are_rejected_if_configured() {
+            let transaction_payload = TransactionPayload::new("id", "type", "msg|with|pipes".as_bytes(), 1);
+
+            let result = pipe_separated::Format::default().with_delimiter_in_fields_rejected().serialize(&transaction_payload);
+
+            assert!(result.is_err())
+        }
+    }
+
+    mod parsing_into_existing_payloads {
+        use crate::payloads::{pipe_separated, TransactionPayload};
+
+        #[test]
+        fn a_reused_payload_is_fully_overwritten() {
+            let format = pipe_separated::Format::default();
+            let mut payload = TransactionPayload::default();
+
+            format.deserialize_into("first agent|first type|first message|1".as_bytes(), &mut payload)
+                .expect("Error parsing payload");
+            format.deserialize_into("id|type|msg|2".as_bytes(), &mut payload)
+                .expect("Error parsing payload");
+
+            assert_eq!(payload, TransactionPayload::new("id", "type", "msg".as_bytes(), 2))
+        }
+
+        #[test]
+        fn invalid_payloads_are_reported() {
+            let mut payload = TransactionPayload::default();
+
+            let result = pipe_separated::Format::default().deserialize_into("id|type".as_bytes(), &mut payload);
+
+            assert!(result.is_err())
+        }
+    }
+
+    mod strict_delimiters {
+        use crate::payloads::{pipe_separated, Error, Format, TransactionPayload};
+
+        #[test]
+        fn a_trailing_delimiter_adds_an_empty_field_by_default() {
+            let result = pipe_separated::Format::default().deserialize("id|type|msg|1|".as_bytes());
+
+            match result {
+                Err(Error::InvalidPayload(_, _)) => (),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
+
+        #[test]
+        fn a_leading_delimiter_is_rejected() {
+            let format = pipe_separated::Format::default().with_strict_delimiters();
+
+            let result = format.deserialize("|id|type|msg|1".as_bytes());
+
+            match result {
+                Err(Error::InvalidPayload(_, _)) => (),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
+
+        #[test]
+        fn a_trailing_delimiter_is_rejected() {
+            let format = pipe_separated::Format::default().with_strict_delimiters();
+
+            let result = format.deserialize("id|type|msg|1|".as_bytes());
+
+            match result {
+                Err(Error::InvalidPayload(_, _)) => (),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn the_rejection_names_the_offending_delimiter() {
+            let format = pipe_separated::Format::default().with_strict_delimiters();
+
+            let leading = format.deserialize("|id|type|msg|1".as_bytes()).expect_err("Expected an error");
+            let trailing = format.deserialize("id|type|msg|1|".as_bytes()).expect_err("Expected an error");
+
+            assert_eq!(leading.to_string(), "Payload starts with a delimiter");
+            assert_eq!(trailing.to_string(), "Payload ends with a delimiter");
+        }
+
+        #[test]
+        fn well_formed_payloads_are_accepted() {
+            let format = pipe_separated::Format::default().with_strict_delimiters();
+
+            let payload = format.deserialize("id|type|msg|1".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(payload, TransactionPayload::new("id", "type", "msg".as_bytes(), 1))
+        }
+    }
+
+    mod nul_terminated_messages {
+        use crate::payloads::{pipe_separated, Format, TransactionPayload};
+
+        #[test]
+        fn a_trailing_nul_is_kept_by_default() {
+            let payload = pipe_separated::Format::default().deserialize("id|type|msg\0|1".as_bytes())
+                .expect("Error parsing payload");
+
+            assert_eq!(payload.message_bytes, "msg\0".as_bytes())
+        }
+
+        #[test]
+        fn a_trailing_nul_is_stripped_if_configured() {
+            let format = pipe_separated::Format::default().with_trailing_nul_strip(true);
+
+            let payload = format.deserialize("id|type|msg\0|1".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(payload.message_bytes, "msg".as_bytes())
+        }
+
+        #[test]
+        fn only_a_single_trailing_nul_is_stripped() {
+            let format = pipe_separated::Format::default().with_trailing_nul_strip(true);
+
+            let payload = format.deserialize("id|type|msg\0\0|1".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(payload.message_bytes, "msg\0".as_bytes())
+        }
+
+        #[test]
+        fn an_interior_nul_is_preserved() {
+            let format = pipe_separated::Format::default().with_trailing_nul_strip(true);
+
+            let payload = format.deserialize("id|type|m\0sg|1".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(payload.message_bytes, "m\0sg".as_bytes())
+        }
+
+        #[test]
+        fn a_trailing_nul_is_appended_if_configured() {
+            let format = pipe_separated::Format::default()
+                .with_trailing_nul_append(true)
+                .with_trailing_nul_strip(true);
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+
+            assert_eq!(serialized_payload, "id|type|msg\0|1".as_bytes().to_vec());
+            assert_eq!(format.deserialize(&serialized_payload).expect("Error parsing payload"), transaction_payload);
+        }
+    }
+
+    mod length_prefixed_messages {
+        use crate::payloads::{pipe_separated, Format, TransactionPayload};
+
+        #[test]
+        fn messages_are_prefixed_with_their_length() {
+            let format = pipe_separated::Format::default().with_length_prefixed_message();
+            let transaction_payload = TransactionPayload::new("id", "type", "a|b".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+
+            assert_eq!(serialized_payload, "id|type|3:a|b|1".as_bytes().to_vec())
+        }
+
+        #[test]
+        fn messages_containing_the_delimiter_round_trip() {
+            let format = pipe_separated::Format::default().with_length_prefixed_message();
+            let transaction_payload = TransactionPayload::new("id", "type", "|a||b|".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Error parsing payload");
+
+            assert_eq!(result, transaction_payload)
+        }
+
+        #[test]
+        fn a_length_overrunning_the_payload_is_rejected() {
+            let format = pipe_separated::Format::default().with_length_prefixed_message();
+
+            let result = format.deserialize("id|type|20:msg|1".as_bytes());
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn a_message_without_length_prefix_is_rejected() {
+            let format = pipe_separated::Format::default().with_length_prefixed_message();
+
+            let result = format.deserialize("id|type|msg|1".as_bytes());
+
+            assert!(result.is_err())
+        }
+    }
+
+    mod timestamps {
+        use crate::payloads::{pipe_separated, Error, Format};
+
+        #[test]
+        fn a_plain_timestamp_is_accepted() {
+            let result = pipe_separated::Format::default().with_leading_zeros_rejected()
+                .deserialize("id|type|msg|1050".as_bytes());
+
+            assert_eq!(result.expect("Error parsing payload").timestamp, 1050)
+        }
+
+        #[test]
+        fn a_signed_timestamp_is_rejected() {
+            let format = pipe_separated::Format::default();
+
+            assert!(matches!(format.deserialize("id|type|msg|+5".as_bytes()), Err(Error::InvalidTimestamp)));
+            assert!(matches!(format.deserialize("id|type|msg|-5".as_bytes()), Err(Error::InvalidTimestamp)));
+        }
+
+        #[test]
+        fn leading_zeros_are_accepted_by_default() {
+            let result = pipe_separated::Format::default().deserialize("id|type|msg|007".as_bytes());
+
+            assert_eq!(result.expect("Error parsing payload").timestamp, 7)
+        }
+
+        #[test]
+        fn leading_zeros_can_be_rejected() {
+            let format = pipe_separated::Format::default().with_leading_zeros_rejected();
+
+            assert!(matches!(format.deserialize("id|type|msg|007".as_bytes()), Err(Error::InvalidTimestamp)));
+            assert_eq!(format.deserialize("id|type|msg|0".as_bytes()).expect("Error parsing payload").timestamp, 0);
+        }
+    }
+
+    mod known_message_types {
+        use crate::payloads::{pipe_separated, Format};
+
+        #[test]
+        fn unknown_message_types_are_accepted_by_default() {
+            let result = pipe_separated::Format::default().deserialize("id|Custom|msg|1".as_bytes());
+
+            assert!(result.is_ok())
+        }
+
+        #[test]
+        fn known_message_types_are_accepted_when_restricted() {
+            let format = pipe_separated::Format::default().with_known_message_types_only();
+
+            let result = format.deserialize("id|RoleSwitch|msg|1".as_bytes());
+
+            assert_eq!(result.expect("Error parsing payload").message_type, "RoleSwitch")
+        }
+
+        #[test]
+        fn unknown_message_types_are_rejected_when_restricted() {
+            let format = pipe_separated::Format::default().with_known_message_types_only();
+
+            let result = format.deserialize("id|Custom|msg|1".as_bytes());
+
+            assert!(result.is_err())
+        }
+    }
+
+    mod field_counts {
+        use crate::payloads::{pipe_separated, Format, TransactionPayload};
+
+        #[test]
+        fn four_fields_are_expected_by_default() {
+            let format = pipe_separated::Format::default();
+
+            let result = format.deserialize("id|type|msg|1".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(format.expected_field_count(), pipe_separated::DEFAULT_FIELD_COUNT);
+            assert_eq!(result, TransactionPayload::new("id", "type", "msg".as_bytes(), 1));
+        }
+
+        #[test]
+        fn a_fifth_field_is_rejected_by_default() {
+            let result = pipe_separated::Format::default().deserialize("id|type|msg|1|alica".as_bytes());
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn five_fields_are_expected_with_a_family_name_segment() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+
+            let result = format.deserialize("id|type|msg|1|alica".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(format.expected_field_count(), 5);
+            assert_eq!(result, TransactionPayload::new("id", "type", "msg".as_bytes(), 1).with_family_name("alica"));
+        }
+
+        #[test]
+        fn configured_fields_beyond_the_known_ones_are_accepted() {
+            let format = pipe_separated::Format::default().with_expected_field_count(5);
+
+            let result = format.deserialize("id|type|msg|1|2".as_bytes()).expect("Error parsing payload");
+
+            assert_eq!(result, TransactionPayload::new("id", "type", "msg".as_bytes(), 1))
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_fifth_field_is_reported_with_both_counts_by_default() {
+            let error = pipe_separated::Format::default().deserialize("id|type|msg|1|alica".as_bytes())
+                .expect_err("Expected an error");
+
+            assert_eq!(error.to_string(), "Payload has 5 fields, expected 4")
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_missing_fifth_field_is_reported_with_both_counts() {
+            let format = pipe_separated::Format::default().with_expected_field_count(5);
+
+            let error = format.deserialize("id|type|msg|1".as_bytes()).expect_err("Expected an error");
+
+            assert_eq!(error.to_string(), "Payload has 4 fields, expected 5")
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn a_sixth_field_is_reported_with_both_counts() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+
+            let error = format.deserialize("id|type|msg|1|alica|1".as_bytes()).expect_err("Expected an error");
+
+            assert_eq!(error.to_string(), "Payload has 6 fields, expected 5")
+        }
+    }
+
+    mod family_names {
+        use crate::payloads::{pipe_separated, Format, TransactionPayload};
+
+        #[test]
+        fn payloads_with_a_family_name_have_five_parts() {
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1)
+                .with_family_name("alica");
+
+            let result = pipe_separated::Format::default().with_family_name_segment().serialize(&transaction_payload)
+                .expect("Could not serialize payload");
+
+            assert_eq!(result, "id|type|msg|1|alica".as_bytes().to_vec())
+        }
+
+        #[test]
+        fn payloads_with_a_family_name_round_trip() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1)
+                .with_family_name("alica");
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Error parsing payload");
+
+            assert_eq!(result, transaction_payload)
+        }
+
+        #[test]
+        fn payloads_without_a_family_name_round_trip() {
+            let format = pipe_separated::Format::default();
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Error parsing payload");
+
+            assert_eq!(serialized_payload, "id|type|msg|1".as_bytes().to_vec());
+            assert_eq!(result.family_name, None);
+        }
+
+        #[test]
+        fn length_prefixed_payloads_with_a_family_name_round_trip() {
+            let format = pipe_separated::Format::default().with_length_prefixed_message().with_family_name_segment();
+            let transaction_payload = TransactionPayload::new("id", "type", "a|b".as_bytes(), 1)
+                .with_family_name("alica");
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Error parsing payload");
+
+            assert_eq!(serialized_payload, "id|type|3:a|b|1|alica".as_bytes().to_vec());
+            assert_eq!(result, transaction_payload);
+        }
+
+        #[test]
+        fn a_fifth_segment_is_not_read_as_family_name_by_default() {
+            let result = pipe_separated::Format::default().deserialize("id|type|msg|1|alica".as_bytes());
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn four_field_payloads_are_parsed_without_a_family_name() {
+            let result = pipe_separated::Format::default().with_family_name_segment().deserialize("id|type|msg|1".as_bytes())
+                .expect("Error parsing payload");
+
+            assert_eq!(result, TransactionPayload::new("id", "type", "msg".as_bytes(), 1))
+        }
+
+        #[test]
+        fn payloads_without_a_family_name_keep_four_fields_in_segment_mode() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+            let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+            let result = format.deserialize(&serialized_payload).expect("Error parsing payload");
+
+            assert_eq!(serialized_payload, "id|type|msg|1".as_bytes().to_vec());
+            assert_eq!(result, transaction_payload);
+        }
+
+        #[test]
+        fn invalid_family_name_segments_are_rejected() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+
+            for payload in ["id|type|msg|1|", "id|type|msg|1|al ica", "id|type|msg|1|al\u{0}ica"] {
+                assert!(format.deserialize(payload.as_bytes()).is_err())
+            }
+        }
+
+        #[test]
+        fn family_names_containing_the_delimiter_are_rejected() {
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1)
+                .with_family_name("al|ica");
+
+            let result = pipe_separated::Format::default().with_family_name_segment().serialize(&transaction_payload);
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn family_names_are_not_dropped_by_formats_without_the_segment() {
+            let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1)
+                .with_family_name("alica");
+
+            let result = pipe_separated::Format::default().serialize(&transaction_payload);
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn the_family_name_is_not_mistaken_for_the_timestamp_when_peeking() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+
+            let result = format.peek_timestamp("id|type|msg|42|alica".as_bytes());
+
+            assert_eq!(result.expect("Could not peek timestamp"), 42)
+        }
+
+        #[test]
+        fn the_timestamp_is_peeked_from_four_field_payloads_in_segment_mode() {
+            let format = pipe_separated::Format::default().with_family_name_segment();
+
+            let result = format.peek_timestamp("id|type|msg|42".as_bytes());
+
+            assert_eq!(result.expect("Could not peek timestamp"), 42)
+        }
+    }
+
+    mod partial_parsing {
+        use crate::payloads::pipe_separated;
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::payloads::Error;
+
+        #[test]
+        fn all_fields_are_present_for_a_valid_payload() {
+            let (partial_payload, error) = pipe_separated::Format::default().deserialize_partial("id|type|msg|1".as_bytes());
+
+            assert!(error.is_none());
+            assert_eq!(partial_payload.agent_id, Some("id".to_string()));
+            assert_eq!(partial_payload.message_type, Some("type".to_string()));
+            assert_eq!(partial_payload.message_bytes, Some("msg".as_bytes().to_vec()));
+            assert_eq!(partial_payload.timestamp, Some(1));
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn fields_parsed_before_a_failure_are_reported() {
+            let (partial_payload, error) = pipe_separated::Format::default().deserialize_partial("id|type".as_bytes());
+
+            assert_eq!(partial_payload.agent_id, Some("id".to_string()));
+            assert_eq!(partial_payload.message_type, Some("type".to_string()));
+            assert_eq!(partial_payload.message_bytes, None);
+            assert_eq!(partial_payload.timestamp, None);
+            match error {
+                Some(Error::InvalidPayload(_, message)) => assert_eq!(message, "Payload contains no message"),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
+    }
+
+    mod peeking {
+        use crate::payloads::{pipe_separated, Format};
+
+        #[test]
+        fn the_peeked_timestamp_equals_the_parsed_one() {
+            let payload_bytes = "id|type|msg|684948894984".as_bytes();
+            let format = pipe_separated::Format::default();
+
+            let timestamp = format.peek_timestamp(payload_bytes).expect("Could not peek timestamp");
+            let payload = format.deserialize(payload_bytes).expect("Error parsing payload");
+
+            assert_eq!(timestamp, payload.timestamp)
+        }
+
+        #[test]
+        fn peeking_fails_if_the_timestamp_is_missing() {
+            let payload_bytes = "id|type|msg".as_bytes();
+
+            let result = pipe_separated::Format::default().peek_timestamp(payload_bytes);
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn the_peeked_message_type_equals_the_parsed_one() {
+            let payload_bytes = "id|RoleSwitch|msg|1".as_bytes();
+            let format = pipe_separated::Format::default();
+
+            let message_type = format.peek_message_type(payload_bytes).expect("Could not peek message type");
+            let payload = format.deserialize(payload_bytes).expect("Error parsing payload");
+
+            assert_eq!(message_type, payload.message_type)
+        }
+
+        #[test]
+        fn peeking_the_message_type_fails_with_fewer_than_two_fields() {
+            let result = pipe_separated::Format::default().peek_message_type("id".as_bytes());
+
+            assert!(result.is_err())
+        }
+
+        #[test]
+        fn the_fields_before_the_timestamp_are_not_parsed_when_peeking() {
+            let payload_bytes = [&b"id|type|"[..], &[0xff, 0xfe], &b"|42"[..]].concat();
+
+            let result = pipe_separated::Format::default().peek_timestamp(&payload_bytes);
+
+            assert_eq!(result.expect("Could not peek timestamp"), 42)
+        }
+
+        #[test]
+        fn peeking_fails_on_payloads_without_delimiters() {
+            let result = pipe_separated::Format::default().peek_timestamp("1".as_bytes());
+
+            assert!(result.is_err())
+        }
     }
 
     pub mod serialization {
         use crate::payloads::{pipe_separated, TransactionPayload, Format};
+        #[cfg(not(feature = "compact-errors"))]
+        use crate::payloads::Error;
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn messages_cut_mid_codepoint_are_reported_as_truncated() {
+            let transaction_payload = TransactionPayload::new("id", "type", &"abä".as_bytes()[..3], 1);
+
+            let result = pipe_separated::Format::default().serialize(&transaction_payload);
+
+            match result {
+                Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "message body is truncated UTF-8 at byte 2"),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
+
+        #[cfg(not(feature = "compact-errors"))]
+        #[test]
+        fn messages_with_invalid_bytes_are_not_reported_as_truncated() {
+            let transaction_payload = TransactionPayload::new("id", "type", &[b'a', 0xff, b'b'], 1);
+
+            let result = pipe_separated::Format::default().serialize(&transaction_payload);
+
+            match result {
+                Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "Message is not a UTF8 String"),
+                _ => panic!("Expected an invalid payload error")
+            }
+        }
 
         #[test]
         fn it_serializes_valid_transaction_payloads() {
@@ -0,0 +1,166 @@
+use prost::Message;
+
+use crate::payloads::{Error, ParsingResult, TransactionPayload, SerializationResult};
+use crate::payloads;
+use crate::helper::error_text;
+use crate::error::ErrorReason;
+
+// Mirrors proto/transaction_payload.proto
+#[derive(Clone, PartialEq, prost::Message)]
+struct ProtobufPayload {
+    #[prost(string, tag = "1")]
+    agent_id: String,
+    #[prost(string, tag = "2")]
+    message_type: String,
+    #[prost(bytes = "vec", tag = "3")]
+    message_bytes: Vec<u8>,
+    #[prost(uint64, tag = "4")]
+    timestamp: u64,
+    #[prost(string, optional, tag = "5")]
+    family_name: Option<String>,
+}
+
+#[derive(Default)]
+pub struct Format {}
+
+impl Format {
+    pub fn new() -> Self {
+        Format {}
+    }
+}
+
+impl payloads::Format for Format {
+    fn serialize(&self, payload: &TransactionPayload) -> SerializationResult {
+        let protobuf_payload = ProtobufPayload {
+            agent_id: payload.agent_id.clone(),
+            message_type: payload.message_type.clone(),
+            message_bytes: payload.message_bytes.clone(),
+            timestamp: payload.timestamp,
+            family_name: payload.family_name.clone(),
+        };
+        if let Some(family_name) = &payload.family_name {
+            payloads::validate_family_name(family_name)?;
+        }
+        Ok(protobuf_payload.encode_to_vec())
+    }
+
+    fn serialize_batch(&self, payloads: &[TransactionPayload]) -> SerializationResult {
+        payloads::log::serialize_length_prefixed(self, payloads)
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> ParsingResult {
+        let protobuf_payload = ProtobufPayload::decode(bytes)
+            .map_err(|_| Error::InvalidPayload(ErrorReason::Malformed, error_text!("Payload is no valid protobuf message")))?;
+
+        if let Some(family_name) = &protobuf_payload.family_name {
+            payloads::validate_family_name(family_name)?;
+        }
+
+        Ok(TransactionPayload {
+            family_name: protobuf_payload.family_name,
+            ..TransactionPayload::new(
+                &protobuf_payload.agent_id,
+                &protobuf_payload.message_type,
+                &protobuf_payload.message_bytes,
+                protobuf_payload.timestamp,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use prost::Message;
+
+    use crate::payloads::{log, protobuf, Format, TransactionPayload};
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct ExtendedProtobufPayload {
+        #[prost(string, tag = "1")]
+        agent_id: String,
+        #[prost(string, tag = "2")]
+        message_type: String,
+        #[prost(bytes = "vec", tag = "3")]
+        message_bytes: Vec<u8>,
+        #[prost(uint64, tag = "4")]
+        timestamp: u64,
+        #[prost(string, tag = "6")]
+        origin: String,
+    }
+
+    #[test]
+    fn serialized_payloads_can_be_read_by_parser() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 684948894984);
+
+        let serialized_payload = protobuf::Format::default().serialize(&transaction_payload)
+            .expect("Could not serialize payload");
+        let result = protobuf::Format::default().deserialize(&serialized_payload)
+            .expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[test]
+    fn binary_messages_survive_a_round_trip() {
+        let transaction_payload = TransactionPayload::new("id", "type", &[0x00, 0xff, b'|', 0x80], 1);
+
+        let serialized_payload = protobuf::Format::default().serialize(&transaction_payload)
+            .expect("Could not serialize payload");
+        let result = protobuf::Format::default().deserialize(&serialized_payload)
+            .expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[test]
+    fn batched_payloads_survive_a_round_trip() {
+        let format = protobuf::Format::default();
+        let payloads = vec![
+            TransactionPayload::new("id", "type", "msg".as_bytes(), 1),
+            TransactionPayload::new("other id", "other type", &[b'\n', 0x00], 2),
+        ];
+
+        let batch = format.serialize_batch(&payloads).expect("Could not serialize batch");
+        let result: Vec<TransactionPayload> = log::split_length_prefixed(&batch).expect("Could not split batch")
+            .into_iter()
+            .map(|record| format.deserialize(record).expect("Could not parse record"))
+            .collect();
+
+        assert_eq!(result, payloads)
+    }
+
+    #[test]
+    fn payloads_with_a_family_name_survive_a_round_trip() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1).with_family_name("alica");
+
+        let serialized_payload = protobuf::Format::default().serialize(&transaction_payload)
+            .expect("Could not serialize payload");
+        let result = protobuf::Format::default().deserialize(&serialized_payload)
+            .expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored_when_parsing() {
+        let extended_payload = ExtendedProtobufPayload {
+            agent_id: "id".to_string(),
+            message_type: "type".to_string(),
+            message_bytes: "msg".as_bytes().to_vec(),
+            timestamp: 1,
+            origin: "origin".to_string(),
+        };
+
+        let result = protobuf::Format::default().deserialize(&extended_payload.encode_to_vec())
+            .expect("Could not parse payload");
+
+        assert_eq!(result, TransactionPayload::new("id", "type", "msg".as_bytes(), 1))
+    }
+
+    #[test]
+    fn invalid_protobuf_messages_are_not_parsed() {
+        let result = protobuf::Format::default().deserialize(&[0xff, 0xff, 0xff]);
+
+        assert!(result.is_err())
+    }
+}
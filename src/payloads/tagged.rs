@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::payloads::{Error, ParsingResult, TransactionPayload, SerializationResult};
+use crate::payloads;
+use crate::helper::error_text;
+use crate::error::ErrorReason;
+
+pub const PIPE_SEPARATED_TAG: u8 = 0x01;
+pub const PROTOBUF_TAG: u8 = 0x02;
+
+pub struct Format {
+    serialization_tag: u8,
+    formats: HashMap<u8, Box<dyn payloads::Format>>
+}
+
+impl Format {
+    pub fn new(serialization_tag: u8) -> Self {
+        Format {
+            serialization_tag,
+            formats: HashMap::new()
+        }
+    }
+
+    pub fn with_format(mut self, tag: u8, format: Box<dyn payloads::Format>) -> Self {
+        self.formats.insert(tag, format);
+        self
+    }
+
+    fn format_for(&self, tag: u8) -> Result<&dyn payloads::Format, Error> {
+        self.formats.get(&tag)
+            .map(|format| format.as_ref())
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::Unsupported, error_text!("Payload has unknown format tag {:#04x}", tag)))
+    }
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::new(PIPE_SEPARATED_TAG)
+            .with_format(PIPE_SEPARATED_TAG, Box::new(payloads::pipe_separated::Format::default()))
+            .with_format(PROTOBUF_TAG, Box::new(payloads::protobuf::Format::default()))
+    }
+}
+
+impl payloads::Format for Format {
+    fn serialize(&self, payload: &TransactionPayload) -> SerializationResult {
+        let inner_bytes = self.format_for(self.serialization_tag)?.serialize(payload)?;
+
+        let mut output = Vec::with_capacity(inner_bytes.len() + 1);
+        output.push(self.serialization_tag);
+        output.extend_from_slice(&inner_bytes);
+        Ok(output)
+    }
+
+    fn serialize_batch(&self, payloads: &[TransactionPayload]) -> SerializationResult {
+        payloads::log::serialize_length_prefixed(self, payloads)
+    }
+
+    fn deserialize(&self, bytes: &[u8]) -> ParsingResult {
+        let (tag, inner_bytes) = bytes.split_first()
+            .ok_or_else(|| Error::InvalidPayload(ErrorReason::MissingField, error_text!("Payload contains no format tag")))?;
+
+        self.format_for(*tag)?.deserialize(inner_bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::payloads::{tagged, Format, TransactionPayload};
+    #[cfg(not(feature = "compact-errors"))]
+    use crate::payloads::Error;
+
+    #[test]
+    fn serialized_payloads_start_with_the_format_tag() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+
+        let result = tagged::Format::default().serialize(&transaction_payload).expect("Could not serialize payload");
+
+        assert_eq!(result, [&[tagged::PIPE_SEPARATED_TAG], "id|type|msg|1".as_bytes()].concat())
+    }
+
+    #[test]
+    fn serialized_payloads_can_be_read_by_parser() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let format = tagged::Format::default();
+
+        let serialized_payload = format.serialize(&transaction_payload).expect("Could not serialize payload");
+        let result = format.deserialize(&serialized_payload).expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[test]
+    fn the_tag_selects_the_format_used_for_parsing() {
+        let transaction_payload = TransactionPayload::new("id", "type", "msg".as_bytes(), 1);
+        let protobuf_format = tagged::Format::new(tagged::PROTOBUF_TAG)
+            .with_format(tagged::PROTOBUF_TAG, Box::new(crate::payloads::protobuf::Format::default()));
+
+        let serialized_payload = protobuf_format.serialize(&transaction_payload).expect("Could not serialize payload");
+        let result = tagged::Format::default().deserialize(&serialized_payload).expect("Could not parse payload");
+
+        assert_eq!(result, transaction_payload)
+    }
+
+    #[cfg(not(feature = "compact-errors"))]
+    #[test]
+    fn payloads_with_an_unknown_tag_are_rejected() {
+        let result = tagged::Format::default().deserialize(&[0x7f, b'a']);
+
+        match result {
+            Err(Error::InvalidPayload(_, message)) => assert_eq!(message, "Payload has unknown format tag 0x7f"),
+            _ => panic!("Expected an invalid payload error")
+        }
+    }
+
+    #[test]
+    fn empty_payloads_are_rejected() {
+        let result = tagged::Format::default().deserialize(&[]);
+
+        assert!(result.is_err())
+    }
+}